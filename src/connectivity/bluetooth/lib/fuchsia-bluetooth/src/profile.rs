@@ -9,6 +9,7 @@ use {
         self as fidl_bredr, ProfileDescriptor, ATTR_BLUETOOTH_PROFILE_DESCRIPTOR_LIST,
         ATTR_SERVICE_CLASS_ID_LIST,
     },
+    fuchsia_zircon as zx,
     std::{
         cmp::min,
         collections::HashSet,
@@ -90,7 +91,7 @@ pub fn profile_descriptor_to_assigned(profile_desc: &ProfileDescriptor) -> Optio
 
 /// Returns the PSM from the provided `protocol`. Returns None if the protocol
 /// is not L2CAP or does not contain a PSM.
-pub fn psm_from_protocol(protocol: &Vec<ProtocolDescriptor>) -> Option<u16> {
+pub fn psm_from_protocol(protocol: &Vec<ProtocolDescriptor>) -> Option<Psm> {
     for descriptor in protocol {
         if descriptor.protocol == fidl_bredr::ProtocolIdentifier::L2Cap {
             if descriptor.params.len() != 1 {
@@ -98,7 +99,7 @@ pub fn psm_from_protocol(protocol: &Vec<ProtocolDescriptor>) -> Option<u16> {
             }
 
             if let DataElement::Uint16(psm) = descriptor.params[0] {
-                return Some(psm);
+                return Some(Psm(psm));
             }
             return None;
         }
@@ -106,34 +107,170 @@ pub fn psm_from_protocol(protocol: &Vec<ProtocolDescriptor>) -> Option<u16> {
     None
 }
 
-/// Search for a Service Class UUID from a list of attributes (such as returned via Service Search)
+/// Returns true if any descriptor in `protocol` is an RFCOMM descriptor.
+pub fn is_rfcomm_protocol(protocol: &[ProtocolDescriptor]) -> bool {
+    protocol.iter().any(|d| d.protocol == fidl_bredr::ProtocolIdentifier::Rfcomm)
+}
+
+/// Returns the RFCOMM server channel from the provided `protocol`. Returns None if the
+/// protocol does not contain an RFCOMM descriptor, or the descriptor's channel parameter is
+/// missing or out of the valid 1-30 server channel range.
+pub fn server_channel_from_protocol(protocol: &[ProtocolDescriptor]) -> Option<ServerChannel> {
+    for descriptor in protocol {
+        if descriptor.protocol == fidl_bredr::ProtocolIdentifier::Rfcomm {
+            return match descriptor.params.get(0) {
+                Some(DataElement::Uint8(channel)) => ServerChannel::new(*channel),
+                _ => None,
+            };
+        }
+    }
+    None
+}
+
+/// A Protocol/Service Multiplexer (PSM), identifying the L2CAP-layer service underneath a
+/// service record.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Psm(u16);
+
+impl Psm {
+    /// The fixed PSM reserved for the Service Discovery Protocol itself.
+    pub const SDP: Psm = Psm(0x0001);
+    /// The fixed PSM reserved for RFCOMM.
+    pub const RFCOMM: Psm = Psm(0x0003);
+    /// The fixed PSM reserved for the Audio/Video Control Transport Protocol.
+    pub const AVCTP: Psm = Psm(0x0017);
+    /// The fixed PSM reserved for the Audio/Video Distribution Transport Protocol.
+    pub const AVDTP: Psm = Psm(0x0019);
+
+    pub fn new(psm: u16) -> Self {
+        Self(psm)
+    }
+
+    /// Validates `value` against the L2CAP core spec's structural rules for dynamically
+    /// assigned PSMs: the least significant bit of the low byte must be set (odd), and the
+    /// least significant bit of the high byte must be clear. Fixed PSMs (below 0x1000) are
+    /// assigned individually by the Bluetooth SIG and aren't required to follow this pattern,
+    /// so they're accepted unconditionally.
+    pub fn new_checked(value: u16) -> Result<Psm, Error> {
+        let psm = Psm(value);
+        if psm.is_fixed() {
+            return Ok(psm);
+        }
+        let [high, low] = value.to_be_bytes();
+        if low & 0x01 == 1 && high & 0x01 == 0 {
+            Ok(psm)
+        } else {
+            Err(format_err!("invalid dynamic PSM: {:#x}", value))
+        }
+    }
+
+    /// Returns true if this PSM is one of the Bluetooth SIG's reserved fixed PSMs (below
+    /// 0x1000), as opposed to one dynamically assigned from the range above it.
+    pub fn is_fixed(&self) -> bool {
+        self.0 < 0x1000
+    }
+}
+
+impl From<u16> for Psm {
+    fn from(src: u16) -> Psm {
+        Psm(src)
+    }
+}
+
+impl From<Psm> for u16 {
+    fn from(src: Psm) -> u16 {
+        src.0
+    }
+}
+
+impl std::fmt::Display for Psm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An RFCOMM server channel number. Valid server channels are in the range 1-30; see the
+/// Bluetooth RFCOMM specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ServerChannel(u8);
+
+impl ServerChannel {
+    /// Returns `None` if `channel` is outside the valid RFCOMM server channel range (1-30).
+    pub fn new(channel: u8) -> Option<Self> {
+        if (1..=30).contains(&channel) {
+            Some(Self(channel))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<ServerChannel> for u8 {
+    fn from(src: ServerChannel) -> u8 {
+        src.0
+    }
+}
+
+impl std::fmt::Display for ServerChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A service class UUID found in a Service Class ID List attribute. Retains the full UUID even
+/// when it isn't one of the well-known `SERVICE_CLASS_UUIDS`, so round-tripping a record never
+/// silently drops a custom or vendor service class.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceClass {
+    pub uuid: Uuid,
+    pub assigned: Option<AssignedNumber>,
+}
+
+/// If `uuid` is a 32/128-bit encoding of a value in the Bluetooth Base UUID range, returns its
+/// canonical 16-bit short form so long-form encodings of a well-known service class still
+/// resolve to their assigned name.
+fn short_form_uuid(uuid: &Uuid) -> Option<u16> {
+    let mut be = fidl_bt::Uuid::from(uuid).value;
+    be.reverse();
+    if be[4..] == BT_BASE_UUID_BE[4..] && be[0..2] == [0, 0] {
+        Some(u16::from_be_bytes([be[2], be[3]]))
+    } else {
+        None
+    }
+}
+
+fn assigned_number_for(uuid: &Uuid) -> Option<AssignedNumber> {
+    let short = short_form_uuid(uuid)?;
+    SERVICE_CLASS_UUIDS.iter().find(|scn| scn.number == short).cloned()
+}
+
+/// Search for Service Class UUIDs from a list of attributes (such as returned via Service
+/// Search). Every UUID present is retained, including ones with no matching `AssignedNumber` -
+/// this lookup recognizes 16/32/128-bit encodings of a service class UUID interchangeably.
 pub fn find_service_classes(
     attributes: &[fidl_fuchsia_bluetooth_bredr::Attribute],
-) -> Vec<AssignedNumber> {
+) -> Vec<ServiceClass> {
     let attr = match attributes.iter().find(|a| a.id == ATTR_SERVICE_CLASS_ID_LIST) {
         None => return vec![],
         Some(attr) => attr,
     };
     if let fidl_fuchsia_bluetooth_bredr::DataElement::Sequence(elems) = &attr.element {
-        let uuids: Vec<Uuid> = elems
+        elems
             .iter()
             .filter_map(|e| {
                 e.as_ref().and_then(|e| {
                     if let fidl_fuchsia_bluetooth_bredr::DataElement::Uuid(uuid) = **e {
-                        Some(uuid.into())
+                        let uuid: Uuid = uuid.into();
+                        let assigned = assigned_number_for(&uuid);
+                        Some(ServiceClass { uuid, assigned })
                     } else {
                         None
                     }
                 })
             })
-            .collect();
-        SERVICE_CLASS_UUIDS
-            .iter()
-            .filter(|scn| uuids.contains(&Uuid::new16(scn.number)))
-            .cloned()
             .collect()
     } else {
-        return vec![];
+        vec![]
     }
 }
 
@@ -165,10 +302,13 @@ pub fn combine_security_requirements(
 /// This is defined as:
 ///   1) Basic requires fewer resources than ERTM.
 ///   2) A smaller SDU size is more restrictive.
+///   3) A shorter flush timeout is more aggressive (fewer retransmissions buffered).
+///   4) A non-Normal ACL priority wins over Normal; conflicting Source vs Sink priorities
+///      cannot both be satisfied and are an error.
 pub fn combine_channel_parameters(
     params: &ChannelParameters,
     other: &ChannelParameters,
-) -> ChannelParameters {
+) -> Result<ChannelParameters, Error> {
     let channel_mode = match (params.channel_mode, other.channel_mode) {
         (Some(fidl_bredr::ChannelMode::Basic), _) | (_, Some(fidl_bredr::ChannelMode::Basic)) => {
             Some(fidl_bredr::ChannelMode::Basic)
@@ -187,7 +327,28 @@ pub fn combine_channel_parameters(
         (Some(reqs), _) | (_, Some(reqs)) => Some(reqs.clone()),
         _ => None,
     };
-    ChannelParameters { channel_mode, max_rx_sdu_size, security_requirements }
+    let flush_timeout = match (params.flush_timeout, other.flush_timeout) {
+        (Some(t1), Some(t2)) => Some(min(t1, t2)),
+        (Some(t), None) | (None, Some(t)) => Some(t),
+        _ => None,
+    };
+    let acl_priority = match (params.acl_priority, other.acl_priority) {
+        (None, None) => None,
+        (Some(p), None) | (None, Some(p)) => Some(p),
+        (Some(p1), Some(p2)) if p1 == p2 => Some(p1),
+        (Some(fidl_bredr::AclPriority::Normal), Some(p))
+        | (Some(p), Some(fidl_bredr::AclPriority::Normal)) => Some(p),
+        (Some(p1), Some(p2)) => {
+            return Err(format_err!("conflicting ACL priorities: {:?} vs {:?}", p1, p2));
+        }
+    };
+    Ok(ChannelParameters {
+        channel_mode,
+        max_rx_sdu_size,
+        security_requirements,
+        flush_timeout,
+        acl_priority,
+    })
 }
 
 /// The basic building block for elements in a SDP record.
@@ -280,6 +441,363 @@ impl From<&DataElement> for fidl_bredr::DataElement {
     }
 }
 
+/// Error produced when a `DataElement` cannot be converted into the requested Rust type,
+/// e.g. calling `u16::try_from(&element)` on a `DataElement::Str`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataElementConversionError {
+    expected: &'static str,
+    actual: String,
+}
+
+impl DataElementConversionError {
+    fn new(expected: &'static str, actual: &DataElement) -> Self {
+        Self { expected, actual: format!("{:?}", actual) }
+    }
+}
+
+impl std::fmt::Display for DataElementConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot convert DataElement to {}: found {}", self.expected, self.actual)
+    }
+}
+
+impl std::error::Error for DataElementConversionError {}
+
+/// Implements `TryFrom<&DataElement>`/`TryFrom<DataElement>` for an integer type that maps
+/// 1:1 onto a single `DataElement` variant.
+macro_rules! data_element_try_from_int {
+    ($ty:ty, $variant:ident) => {
+        impl TryFrom<&DataElement> for $ty {
+            type Error = DataElementConversionError;
+
+            fn try_from(src: &DataElement) -> Result<$ty, Self::Error> {
+                match src {
+                    DataElement::$variant(x) => Ok(*x),
+                    _ => Err(DataElementConversionError::new(stringify!($ty), src)),
+                }
+            }
+        }
+
+        impl TryFrom<DataElement> for $ty {
+            type Error = DataElementConversionError;
+
+            fn try_from(src: DataElement) -> Result<$ty, Self::Error> {
+                Self::try_from(&src)
+            }
+        }
+    };
+}
+
+data_element_try_from_int!(u8, Uint8);
+data_element_try_from_int!(u16, Uint16);
+data_element_try_from_int!(u32, Uint32);
+data_element_try_from_int!(u64, Uint64);
+data_element_try_from_int!(i8, Int8);
+data_element_try_from_int!(i16, Int16);
+data_element_try_from_int!(i32, Int32);
+data_element_try_from_int!(i64, Int64);
+
+impl TryFrom<&DataElement> for bool {
+    type Error = DataElementConversionError;
+
+    fn try_from(src: &DataElement) -> Result<bool, Self::Error> {
+        match src {
+            DataElement::Bool(b) => Ok(*b),
+            _ => Err(DataElementConversionError::new("bool", src)),
+        }
+    }
+}
+
+impl TryFrom<DataElement> for bool {
+    type Error = DataElementConversionError;
+
+    fn try_from(src: DataElement) -> Result<bool, Self::Error> {
+        Self::try_from(&src)
+    }
+}
+
+impl TryFrom<&DataElement> for String {
+    type Error = DataElementConversionError;
+
+    /// Converts either a `Str` or a `Url` element, since both carry a plain `String` payload.
+    fn try_from(src: &DataElement) -> Result<String, Self::Error> {
+        match src {
+            DataElement::Str(s) | DataElement::Url(s) => Ok(s.clone()),
+            _ => Err(DataElementConversionError::new("String", src)),
+        }
+    }
+}
+
+impl TryFrom<DataElement> for String {
+    type Error = DataElementConversionError;
+
+    fn try_from(src: DataElement) -> Result<String, Self::Error> {
+        Self::try_from(&src)
+    }
+}
+
+impl TryFrom<&DataElement> for Uuid {
+    type Error = DataElementConversionError;
+
+    fn try_from(src: &DataElement) -> Result<Uuid, Self::Error> {
+        match src {
+            DataElement::Uuid(uuid) => Ok(Uuid::from(uuid.clone())),
+            _ => Err(DataElementConversionError::new("Uuid", src)),
+        }
+    }
+}
+
+impl TryFrom<DataElement> for Uuid {
+    type Error = DataElementConversionError;
+
+    fn try_from(src: DataElement) -> Result<Uuid, Self::Error> {
+        Self::try_from(&src)
+    }
+}
+
+impl TryFrom<&DataElement> for Vec<DataElement> {
+    type Error = DataElementConversionError;
+
+    /// Flattens either a `Sequence` or an `Alternatives` element into a plain `Vec`.
+    fn try_from(src: &DataElement) -> Result<Vec<DataElement>, Self::Error> {
+        match src {
+            DataElement::Sequence(seq) | DataElement::Alternatives(seq) => {
+                Ok(seq.iter().map(|elem| (**elem).clone()).collect())
+            }
+            _ => Err(DataElementConversionError::new("Vec<DataElement>", src)),
+        }
+    }
+}
+
+impl TryFrom<DataElement> for Vec<DataElement> {
+    type Error = DataElementConversionError;
+
+    fn try_from(src: DataElement) -> Result<Vec<DataElement>, Self::Error> {
+        Self::try_from(&src)
+    }
+}
+
+impl DataElement {
+    /// Returns the children of a `Sequence` or `Alternatives` element, so callers can iterate
+    /// a list-shaped attribute without matching on the enum themselves.
+    pub fn as_sequence(&self) -> Result<&[Box<DataElement>], DataElementConversionError> {
+        match self {
+            DataElement::Sequence(seq) | DataElement::Alternatives(seq) => Ok(seq),
+            _ => Err(DataElementConversionError::new("Sequence", self)),
+        }
+    }
+
+    /// Encodes this element into its raw SDP data-element wire representation: a header byte
+    /// (5-bit type descriptor, 3-bit size index), an optional length field, then the payload.
+    /// See `DataElement::decode` for the decoder.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            DataElement::Int8(v) => encode_fixed(TYPE_INT, 0, &v.to_be_bytes()),
+            DataElement::Int16(v) => encode_fixed(TYPE_INT, 1, &v.to_be_bytes()),
+            DataElement::Int32(v) => encode_fixed(TYPE_INT, 2, &v.to_be_bytes()),
+            DataElement::Int64(v) => encode_fixed(TYPE_INT, 3, &v.to_be_bytes()),
+            DataElement::Uint8(v) => encode_fixed(TYPE_UINT, 0, &v.to_be_bytes()),
+            DataElement::Uint16(v) => encode_fixed(TYPE_UINT, 1, &v.to_be_bytes()),
+            DataElement::Uint32(v) => encode_fixed(TYPE_UINT, 2, &v.to_be_bytes()),
+            DataElement::Uint64(v) => encode_fixed(TYPE_UINT, 3, &v.to_be_bytes()),
+            DataElement::Bool(b) => encode_fixed(TYPE_BOOL, 0, &[*b as u8]),
+            DataElement::Uuid(uuid) => {
+                let payload = encode_uuid(uuid);
+                let size_index = match payload.len() {
+                    2 => 1,
+                    4 => 2,
+                    16 => 4,
+                    _ => unreachable!("encode_uuid only produces 2, 4 or 16 byte payloads"),
+                };
+                encode_fixed(TYPE_UUID, size_index, &payload)
+            }
+            DataElement::Str(s) => encode_variable(TYPE_STRING, s.as_bytes()),
+            DataElement::Url(s) => encode_variable(TYPE_URL, s.as_bytes()),
+            DataElement::Sequence(seq) => encode_variable(TYPE_SEQUENCE, &encode_children(seq)),
+            DataElement::Alternatives(seq) => {
+                encode_variable(TYPE_ALTERNATIVE, &encode_children(seq))
+            }
+        }
+    }
+
+    /// Decodes a single `DataElement` from the start of `buf`, returning it along with the
+    /// number of bytes consumed so the caller can continue parsing a sequence of elements.
+    ///
+    /// See `encode` for the wire format. Rejects truncated or overlong length fields, and
+    /// errors if a declared payload length would run past the end of `buf`.
+    pub fn decode(buf: &[u8]) -> Result<(DataElement, usize), Error> {
+        let header = *buf.get(0).ok_or_else(|| format_err!("buffer too short for a header byte"))?;
+        let type_desc = header >> 3;
+        let size_index = header & 0x7;
+
+        let (payload_len, header_len): (usize, usize) = match size_index {
+            0 => (1, 1),
+            1 => (2, 1),
+            2 => (4, 1),
+            3 => (8, 1),
+            4 => (16, 1),
+            5 => {
+                let len = *buf
+                    .get(1)
+                    .ok_or_else(|| format_err!("buffer too short for a 1-byte length field"))?;
+                (len as usize, 2)
+            }
+            6 => {
+                let bytes = buf
+                    .get(1..3)
+                    .ok_or_else(|| format_err!("buffer too short for a 2-byte length field"))?;
+                (u16::from_be_bytes([bytes[0], bytes[1]]) as usize, 3)
+            }
+            7 => {
+                let bytes = buf
+                    .get(1..5)
+                    .ok_or_else(|| format_err!("buffer too short for a 4-byte length field"))?;
+                (u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize, 5)
+            }
+            _ => unreachable!("size index is a 3-bit field"),
+        };
+
+        let payload_end = header_len
+            .checked_add(payload_len)
+            .ok_or_else(|| format_err!("DataElement length field overflowed"))?;
+        if buf.len() < payload_end {
+            return Err(format_err!("DataElement payload runs past the end of the buffer"));
+        }
+        let payload = &buf[header_len..payload_end];
+
+        let elem = match type_desc {
+            TYPE_NIL => return Err(format_err!("Nil DataElement is not representable")),
+            TYPE_UINT => decode_uint(payload)?,
+            TYPE_INT => decode_int(payload)?,
+            TYPE_UUID => DataElement::Uuid(decode_uuid(payload)?),
+            TYPE_STRING => DataElement::Str(
+                String::from_utf8(payload.to_vec())
+                    .map_err(|e| format_err!("invalid UTF-8 in Str element: {}", e))?,
+            ),
+            TYPE_BOOL => {
+                if payload.len() != 1 {
+                    return Err(format_err!("Boolean DataElement must have a 1-byte payload"));
+                }
+                DataElement::Bool(payload[0] != 0)
+            }
+            TYPE_SEQUENCE => DataElement::Sequence(decode_children(payload)?),
+            TYPE_ALTERNATIVE => DataElement::Alternatives(decode_children(payload)?),
+            TYPE_URL => DataElement::Url(
+                String::from_utf8(payload.to_vec())
+                    .map_err(|e| format_err!("invalid UTF-8 in Url element: {}", e))?,
+            ),
+            other => return Err(format_err!("unknown DataElement type descriptor: {}", other)),
+        };
+
+        Ok((elem, payload_end))
+    }
+}
+
+// SDP data-element type descriptors (the top 5 bits of the header byte).
+const TYPE_NIL: u8 = 0;
+const TYPE_UINT: u8 = 1;
+const TYPE_INT: u8 = 2;
+const TYPE_UUID: u8 = 3;
+const TYPE_STRING: u8 = 4;
+const TYPE_BOOL: u8 = 5;
+const TYPE_SEQUENCE: u8 = 6;
+const TYPE_ALTERNATIVE: u8 = 7;
+const TYPE_URL: u8 = 8;
+
+/// The Bluetooth Base UUID (00000000-0000-1000-8000-00805F9B34FB), in big-endian byte order.
+/// A 16-bit or 32-bit "short form" UUID is only valid when the trailing bytes match this.
+const BT_BASE_UUID_BE: [u8; 16] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0x80, 0x5F, 0x9B, 0x34, 0xFB,
+];
+
+/// Encodes a header byte plus a fixed-size payload (size index 0-4: no separate length field).
+fn encode_fixed(type_desc: u8, size_index: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = vec![(type_desc << 3) | size_index];
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Encodes a header byte, the smallest length field that fits `payload`'s size (size index
+/// 5/6/7: a 1/2/4-byte big-endian length), then the payload itself.
+fn encode_variable(type_desc: u8, payload: &[u8]) -> Vec<u8> {
+    let len = payload.len();
+    let (size_index, len_bytes): (u8, Vec<u8>) = if len <= u8::MAX as usize {
+        (5, vec![len as u8])
+    } else if len <= u16::MAX as usize {
+        (6, (len as u16).to_be_bytes().to_vec())
+    } else {
+        (7, (len as u32).to_be_bytes().to_vec())
+    };
+    let mut out = vec![(type_desc << 3) | size_index];
+    out.extend_from_slice(&len_bytes);
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Concatenates the wire encoding of each child element, for `Sequence`/`Alternatives` payloads.
+fn encode_children(children: &[Box<DataElement>]) -> Vec<u8> {
+    children.iter().flat_map(|child| child.encode()).collect()
+}
+
+/// Encodes a UUID in the shortest form the SDP wire format allows: 2 bytes if it's a 16-bit
+/// Bluetooth-assigned UUID, 4 bytes if 32-bit, otherwise the full 16-byte UUID.
+fn encode_uuid(uuid: &fidl_bt::Uuid) -> Vec<u8> {
+    let mut be = uuid.value;
+    be.reverse();
+    if be[4..] == BT_BASE_UUID_BE[4..] {
+        if be[0..2] == [0, 0] {
+            return be[2..4].to_vec();
+        }
+        return be[0..4].to_vec();
+    }
+    be.to_vec()
+}
+
+/// Reconstructs a full 128-bit UUID from a 2, 4, or 16-byte big-endian SDP payload.
+fn decode_uuid(payload: &[u8]) -> Result<fidl_bt::Uuid, Error> {
+    let mut be = BT_BASE_UUID_BE;
+    match payload.len() {
+        2 => be[2..4].copy_from_slice(payload),
+        4 => be[0..4].copy_from_slice(payload),
+        16 => be.copy_from_slice(payload),
+        _ => return Err(format_err!("invalid UUID payload length: {}", payload.len())),
+    }
+    be.reverse();
+    Ok(fidl_bt::Uuid { value: be })
+}
+
+fn decode_uint(payload: &[u8]) -> Result<DataElement, Error> {
+    match payload.len() {
+        1 => Ok(DataElement::Uint8(payload[0])),
+        2 => Ok(DataElement::Uint16(u16::from_be_bytes(payload.try_into().unwrap()))),
+        4 => Ok(DataElement::Uint32(u32::from_be_bytes(payload.try_into().unwrap()))),
+        8 => Ok(DataElement::Uint64(u64::from_be_bytes(payload.try_into().unwrap()))),
+        _ => Err(format_err!("invalid unsigned int payload length: {}", payload.len())),
+    }
+}
+
+fn decode_int(payload: &[u8]) -> Result<DataElement, Error> {
+    match payload.len() {
+        1 => Ok(DataElement::Int8(payload[0] as i8)),
+        2 => Ok(DataElement::Int16(i16::from_be_bytes(payload.try_into().unwrap()))),
+        4 => Ok(DataElement::Int32(i32::from_be_bytes(payload.try_into().unwrap()))),
+        8 => Ok(DataElement::Int64(i64::from_be_bytes(payload.try_into().unwrap()))),
+        _ => Err(format_err!("invalid signed int payload length: {}", payload.len())),
+    }
+}
+
+/// Parses a run of back-to-back `DataElement`s out of a `Sequence`/`Alternatives` payload.
+fn decode_children(payload: &[u8]) -> Result<Vec<Box<DataElement>>, Error> {
+    let mut children = vec![];
+    let mut offset = 0;
+    while offset < payload.len() {
+        let (elem, consumed) = DataElement::decode(&payload[offset..])?;
+        children.push(Box::new(elem));
+        offset += consumed;
+    }
+    Ok(children)
+}
+
 /// Information about a communications protocol.
 /// Corresponds directly to the FIDL `ProtocolDescriptor` definition - with the extra
 /// properties of Clone and PartialEq.
@@ -390,12 +908,12 @@ pub struct ServiceDefinition {
 
 impl ServiceDefinition {
     /// Returns the primary PSM associated with this ServiceDefinition.
-    pub fn primary_psm(&self) -> Option<u16> {
+    pub fn primary_psm(&self) -> Option<Psm> {
         psm_from_protocol(&self.protocol_descriptor_list)
     }
 
     /// Returns the additional PSMs associated with this ServiceDefinition.
-    pub fn additional_psms(&self) -> HashSet<u16> {
+    pub fn additional_psms(&self) -> HashSet<Psm> {
         self.additional_protocol_descriptor_lists
             .iter()
             .filter_map(|protocol| psm_from_protocol(protocol))
@@ -406,11 +924,339 @@ impl ServiceDefinition {
     ///
     /// It's possible that the definition doesn't provide any PSMs, in which
     /// case the returned set will be empty.
-    pub fn psm_set(&self) -> HashSet<u16> {
+    pub fn psm_set(&self) -> HashSet<Psm> {
         let mut psms = self.additional_psms();
         self.primary_psm().map(|psm| psms.insert(psm));
         psms
     }
+
+    /// Returns the subset of `psm_set()` that fails `Psm::new_checked`'s structural validity
+    /// rules, so a malformed remote record can be flagged rather than propagated blindly.
+    pub fn invalid_psms(&self) -> HashSet<Psm> {
+        self.psm_set().into_iter().filter(|psm| Psm::new_checked(u16::from(*psm)).is_err()).collect()
+    }
+
+    /// Returns the RFCOMM server channel associated with this ServiceDefinition's primary
+    /// protocol, if it advertises RFCOMM.
+    pub fn server_channel(&self) -> Option<ServerChannel> {
+        server_channel_from_protocol(&self.protocol_descriptor_list)
+    }
+
+    /// Returns the RFCOMM server channel associated with this ServiceDefinition, checking the
+    /// primary protocol descriptor list first and falling back to each additional protocol
+    /// descriptor list in order. Unlike `server_channel`, this also finds records that only
+    /// advertise RFCOMM through an additional (non-primary) protocol stack.
+    pub fn rfcomm_channel(&self) -> Option<ServerChannel> {
+        self.server_channel().or_else(|| {
+            self.additional_protocol_descriptor_lists
+                .iter()
+                .find_map(|protocol| server_channel_from_protocol(protocol))
+        })
+    }
+
+    /// Returns the additional attribute with the given `id`, if present.
+    pub fn attribute(&self, id: u16) -> Option<&Attribute> {
+        self.additional_attributes.iter().find(|attr| attr.id == id)
+    }
+
+    /// Finds the attribute identified by `attribute_id` and interprets its element as a
+    /// `Uint16` feature bitfield, as used by profiles like A2DP and AVRCP to advertise
+    /// supported-features. Returns `None` if the attribute is missing or isn't a `Uint16`.
+    pub fn supported_features(&self, attribute_id: u16) -> Option<u16> {
+        self.attribute(attribute_id).and_then(|attr| u16::try_from(&attr.element).ok())
+    }
+
+    /// Stamps `channel` into the RFCOMM descriptor's parameter within the primary protocol
+    /// descriptor list, replacing any placeholder channel that was there before. No-op if the
+    /// primary protocol descriptor list doesn't advertise RFCOMM.
+    pub fn set_server_channel(&mut self, channel: ServerChannel) {
+        for descriptor in self.protocol_descriptor_list.iter_mut() {
+            if descriptor.protocol == fidl_bredr::ProtocolIdentifier::Rfcomm {
+                descriptor.params = vec![DataElement::Uint8(channel.into())];
+                return;
+            }
+        }
+    }
+
+    /// Merges `self` with `other`, producing a combined `ServiceDefinition`. This lets a
+    /// component compose a base service record with profile-specific overlays rather than
+    /// re-implementing ad-hoc deduplication at each call site:
+    ///   - `service_class_uuids` are unioned, deduplicated.
+    ///   - `additional_protocol_descriptor_lists` and `additional_attributes` are concatenated,
+    ///     except that two `additional_attributes` sharing an id but disagreeing on element is
+    ///     an error.
+    ///   - `information` entries are merged keyed by language; two non-empty entries for the
+    ///     same language that differ is an error.
+    ///   - `profile_descriptors` are deduplicated by `profile_id`, keeping the higher
+    ///     (major, minor) version when both sides declare the same profile.
+    ///
+    /// The primary `protocol_descriptor_list` is not merged - `self`'s is kept as-is, since
+    /// there's no sound way to combine two primary transports into one.
+    pub fn merge(&self, other: &ServiceDefinition) -> Result<ServiceDefinition, Error> {
+        let mut service_class_uuids = self.service_class_uuids.clone();
+        for uuid in &other.service_class_uuids {
+            if !service_class_uuids.contains(uuid) {
+                service_class_uuids.push(uuid.clone());
+            }
+        }
+
+        let mut additional_protocol_descriptor_lists =
+            self.additional_protocol_descriptor_lists.clone();
+        additional_protocol_descriptor_lists
+            .extend(other.additional_protocol_descriptor_lists.iter().cloned());
+
+        let mut additional_attributes = self.additional_attributes.clone();
+        for attr in &other.additional_attributes {
+            match additional_attributes.iter().find(|a| a.id == attr.id) {
+                Some(existing) if existing.element != attr.element => {
+                    return Err(format_err!(
+                        "conflicting values for attribute {:#x}: {:?} vs {:?}",
+                        attr.id,
+                        existing.element,
+                        attr.element
+                    ));
+                }
+                Some(_) => {}
+                None => additional_attributes.push(attr.clone()),
+            }
+        }
+
+        let mut information = self.information.clone();
+        for info in &other.information {
+            match information.iter().find(|i| i.language == info.language) {
+                Some(existing) if existing != info => {
+                    return Err(format_err!(
+                        "conflicting Information entries for language {:?}",
+                        info.language
+                    ));
+                }
+                Some(_) => {}
+                None => information.push(info.clone()),
+            }
+        }
+
+        let mut profile_descriptors: Vec<fidl_bredr::ProfileDescriptor> =
+            self.profile_descriptors.clone();
+        for desc in &other.profile_descriptors {
+            match profile_descriptors.iter_mut().find(|d| d.profile_id == desc.profile_id) {
+                Some(existing) => {
+                    if (desc.major_version, desc.minor_version)
+                        > (existing.major_version, existing.minor_version)
+                    {
+                        existing.major_version = desc.major_version;
+                        existing.minor_version = desc.minor_version;
+                    }
+                }
+                None => profile_descriptors.push(desc.clone()),
+            }
+        }
+
+        Ok(ServiceDefinition {
+            service_class_uuids,
+            protocol_descriptor_list: self.protocol_descriptor_list.clone(),
+            additional_protocol_descriptor_lists,
+            profile_descriptors,
+            information,
+            additional_attributes,
+        })
+    }
+}
+
+/// Builds a `ServiceDefinition`, validating invariants that are easy to get wrong when
+/// constructing a record by hand: at least one service class UUID, an RFCOMM record carrying
+/// an empty L2CAP protocol descriptor ahead of its server-channel descriptor, and profile
+/// versions that are encodable as the big-endian `Uint16` pair `elem_to_profile_descriptor`
+/// expects.
+#[derive(Clone, Debug, Default)]
+pub struct ServiceDefinitionBuilder {
+    service_class_uuids: Vec<Uuid>,
+    protocol_descriptor_list: Vec<ProtocolDescriptor>,
+    additional_protocol_descriptor_lists: Vec<Vec<ProtocolDescriptor>>,
+    profile_descriptors: Vec<ProfileDescriptor>,
+    information: Vec<Information>,
+    additional_attributes: Vec<Attribute>,
+}
+
+impl ServiceDefinitionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a service class UUID. At least one is required for `build` to succeed.
+    pub fn service_class_uuid(mut self, uuid: Uuid) -> Self {
+        self.service_class_uuids.push(uuid);
+        self
+    }
+
+    /// Advertises the service directly over L2CAP at `psm`.
+    pub fn psm(mut self, psm: Psm) -> Self {
+        self.protocol_descriptor_list.push(ProtocolDescriptor {
+            protocol: fidl_bredr::ProtocolIdentifier::L2Cap,
+            params: vec![DataElement::Uint16(psm.into())],
+        });
+        self
+    }
+
+    /// Advertises the service over RFCOMM at `channel`, preceded by the empty L2CAP protocol
+    /// descriptor the RFCOMM profile spec requires.
+    pub fn rfcomm(mut self, channel: ServerChannel) -> Self {
+        self.protocol_descriptor_list.push(ProtocolDescriptor {
+            protocol: fidl_bredr::ProtocolIdentifier::L2Cap,
+            params: vec![],
+        });
+        self.protocol_descriptor_list.push(ProtocolDescriptor {
+            protocol: fidl_bredr::ProtocolIdentifier::Rfcomm,
+            params: vec![DataElement::Uint8(channel.into())],
+        });
+        self
+    }
+
+    /// Adds a Bluetooth Profile Descriptor entry for `profile_id` at `(major, minor)`.
+    pub fn profile(
+        mut self,
+        profile_id: fidl_bredr::ServiceClassProfileIdentifier,
+        version: (u8, u8),
+    ) -> Self {
+        let (major_version, minor_version) = version;
+        self.profile_descriptors.push(ProfileDescriptor {
+            profile_id,
+            major_version,
+            minor_version,
+        });
+        self
+    }
+
+    pub fn information(mut self, info: Information) -> Self {
+        self.information.push(info);
+        self
+    }
+
+    pub fn attribute(mut self, attr: Attribute) -> Self {
+        self.additional_attributes.push(attr);
+        self
+    }
+
+    /// Validates the accumulated state and produces the `ServiceDefinition`.
+    pub fn build(self) -> Result<ServiceDefinition, Error> {
+        if self.service_class_uuids.is_empty() {
+            return Err(format_err!("ServiceDefinition requires at least one service class UUID"));
+        }
+
+        if is_rfcomm_protocol(&self.protocol_descriptor_list) {
+            match self.protocol_descriptor_list.get(0) {
+                Some(ProtocolDescriptor {
+                    protocol: fidl_bredr::ProtocolIdentifier::L2Cap,
+                    params,
+                }) if params.is_empty() => {}
+                _ => {
+                    return Err(format_err!(
+                        "RFCOMM records must have an empty L2CAP descriptor before the RFCOMM descriptor"
+                    ))
+                }
+            }
+            if server_channel_from_protocol(&self.protocol_descriptor_list).is_none() {
+                return Err(format_err!(
+                    "RFCOMM records must carry a valid (1-30) server channel parameter"
+                ));
+            }
+        }
+
+        Ok(ServiceDefinition {
+            service_class_uuids: self.service_class_uuids,
+            protocol_descriptor_list: self.protocol_descriptor_list,
+            additional_protocol_descriptor_lists: self.additional_protocol_descriptor_lists,
+            profile_descriptors: self.profile_descriptors,
+            information: self.information,
+            additional_attributes: self.additional_attributes,
+        })
+    }
+}
+
+// 16-bit assigned numbers for the service classes of the standard record types below. See the
+// Bluetooth SIG-assigned numbers document for "Service Discovery Protocol (SDP)".
+const PBAP_PSE_SERVICE_CLASS_UUID: u16 = 0x112F;
+const MAP_MAS_SERVICE_CLASS_UUID: u16 = 0x1132;
+const OPP_SERVICE_CLASS_UUID: u16 = 0x1105;
+const SAP_SERVICE_CLASS_UUID: u16 = 0x112D;
+const DIP_SERVICE_CLASS_UUID: u16 = 0x1200;
+
+/// Builds a standard PBAP PSE (phonebook server) record advertised over `channel`.
+pub fn pbap_pse_service_definition(
+    channel: ServerChannel,
+    version: (u8, u8),
+    information: Option<Information>,
+) -> Result<ServiceDefinition, Error> {
+    let mut builder = ServiceDefinitionBuilder::new()
+        .service_class_uuid(Uuid::new16(PBAP_PSE_SERVICE_CLASS_UUID))
+        .rfcomm(channel)
+        .profile(fidl_bredr::ServiceClassProfileIdentifier::PhonebookAccessPse, version);
+    if let Some(info) = information {
+        builder = builder.information(info);
+    }
+    builder.build()
+}
+
+/// Builds a standard MAP MAS (message server) record advertised over `channel`.
+pub fn map_mas_service_definition(
+    channel: ServerChannel,
+    version: (u8, u8),
+    information: Option<Information>,
+) -> Result<ServiceDefinition, Error> {
+    let mut builder = ServiceDefinitionBuilder::new()
+        .service_class_uuid(Uuid::new16(MAP_MAS_SERVICE_CLASS_UUID))
+        .rfcomm(channel)
+        .profile(fidl_bredr::ServiceClassProfileIdentifier::MessageAccessServer, version);
+    if let Some(info) = information {
+        builder = builder.information(info);
+    }
+    builder.build()
+}
+
+/// Builds a standard OPP (Object Push) record advertised over `channel`.
+pub fn opp_service_definition(
+    channel: ServerChannel,
+    version: (u8, u8),
+    information: Option<Information>,
+) -> Result<ServiceDefinition, Error> {
+    let mut builder = ServiceDefinitionBuilder::new()
+        .service_class_uuid(Uuid::new16(OPP_SERVICE_CLASS_UUID))
+        .rfcomm(channel)
+        .profile(fidl_bredr::ServiceClassProfileIdentifier::ObexObjectPush, version);
+    if let Some(info) = information {
+        builder = builder.information(info);
+    }
+    builder.build()
+}
+
+/// Builds a standard SAP (SIM Access) record advertised over `channel`.
+pub fn sap_service_definition(
+    channel: ServerChannel,
+    version: (u8, u8),
+    information: Option<Information>,
+) -> Result<ServiceDefinition, Error> {
+    let mut builder = ServiceDefinitionBuilder::new()
+        .service_class_uuid(Uuid::new16(SAP_SERVICE_CLASS_UUID))
+        .rfcomm(channel)
+        .profile(fidl_bredr::ServiceClassProfileIdentifier::SimAccess, version);
+    if let Some(info) = information {
+        builder = builder.information(info);
+    }
+    builder.build()
+}
+
+/// Builds a standard DIP (Device ID) record. Unlike the other profiles here, DIP has no
+/// L2CAP/RFCOMM transport of its own - it's discovered purely via its SDP attributes.
+pub fn dip_service_definition(
+    version: (u8, u8),
+    information: Option<Information>,
+) -> Result<ServiceDefinition, Error> {
+    let mut builder = ServiceDefinitionBuilder::new()
+        .service_class_uuid(Uuid::new16(DIP_SERVICE_CLASS_UUID))
+        .profile(fidl_bredr::ServiceClassProfileIdentifier::PnpInformation, version);
+    if let Some(info) = information {
+        builder = builder.information(info);
+    }
+    builder.build()
 }
 
 impl TryFrom<&fidl_bredr::ServiceDefinition> for ServiceDefinition {
@@ -540,6 +1386,11 @@ pub struct ChannelParameters {
     pub channel_mode: Option<fidl_bredr::ChannelMode>,
     pub max_rx_sdu_size: Option<u16>,
     pub security_requirements: Option<SecurityRequirements>,
+    /// How long the remote peer should buffer packets before discarding them, for
+    /// latency-sensitive profiles (e.g. audio streaming) that prefer dropped data over delay.
+    pub flush_timeout: Option<zx::Duration>,
+    /// A hint to prioritize this channel's traffic on the shared ACL link.
+    pub acl_priority: Option<fidl_bredr::AclPriority>,
 }
 
 impl TryFrom<&fidl_bredr::ChannelParameters> for ChannelParameters {
@@ -559,6 +1410,8 @@ impl TryFrom<&fidl_bredr::ChannelParameters> for ChannelParameters {
                 .security_requirements
                 .as_ref()
                 .map(SecurityRequirements::from),
+            flush_timeout: src.flush_timeout.map(zx::Duration::from_nanos),
+            acl_priority: src.acl_priority,
         })
     }
 }
@@ -580,6 +1433,8 @@ impl TryFrom<&ChannelParameters> for fidl_bredr::ChannelParameters {
                 .security_requirements
                 .as_ref()
                 .map(fidl_bredr::SecurityRequirements::from),
+            flush_timeout: src.flush_timeout.map(|t| t.into_nanos()),
+            acl_priority: src.acl_priority,
             ..fidl_bredr::ChannelParameters::EMPTY
         })
     }
@@ -681,7 +1536,7 @@ mod tests {
 
         let result = find_service_classes(&[attribute]);
         assert_eq!(1, result.len());
-        let assigned_num = result.first().unwrap();
+        let assigned_num = result.first().unwrap().assigned.as_ref().unwrap();
         assert_eq!(0x1101, assigned_num.number); // 0x1101 is the 16-bit UUID of SerialPort
         assert_eq!("SerialPort", assigned_num.name);
 
@@ -693,12 +1548,182 @@ mod tests {
             ]),
         };
 
-        // Discards unknown UUIDs
+        // Unknown UUIDs are retained, just without an assigned name.
         let result = find_service_classes(&[unknown_uuids]);
-        assert_eq!(1, result.len());
-        let assigned_num = result.first().unwrap();
-        assert_eq!(0x1101, assigned_num.number); // 0x1101 is the 16-bit UUID of SerialPort
-        assert_eq!("SerialPort", assigned_num.name);
+        assert_eq!(2, result.len());
+        assert_eq!(Uuid::new16(0x1101), result[0].uuid);
+        assert_eq!("SerialPort", result[0].assigned.as_ref().unwrap().name);
+        assert_eq!(Uuid::new16(0xc0de), result[1].uuid);
+        assert_eq!(None, result[1].assigned);
+    }
+
+    #[test]
+    fn test_short_form_uuid_recognizes_base_uuid_derived_values() {
+        // Any UUID derived from the Bluetooth Base UUID with zeroed high bits, regardless of
+        // whether a caller thinks of it as a 16-bit short form or a 32-bit one with the top
+        // half clear, should resolve back to the same 16-bit code.
+        assert_eq!(short_form_uuid(&Uuid::new16(0x1101)), Some(0x1101));
+
+        // A UUID that does not sit within the Bluetooth Base UUID range has no short form.
+        let vendor_uuid: Uuid = fidl_bt::Uuid {
+            value: [
+                0xf0, 0xde, 0xbc, 0x9a, 0x78, 0x56, 0x34, 0x12, 0x78, 0x56, 0x34, 0x12, 0x78,
+                0x56, 0x34, 0x12,
+            ],
+        }
+        .into();
+        assert_eq!(short_form_uuid(&vendor_uuid), None);
+    }
+
+    #[test]
+    fn test_psm_new_checked_validates_dynamic_range() {
+        // Odd low byte, even high byte: valid dynamic PSM.
+        assert!(Psm::new_checked(0x1001).is_ok());
+        // Even low byte: invalid.
+        assert!(Psm::new_checked(0x1002).is_err());
+        // Odd high byte: invalid.
+        assert!(Psm::new_checked(0x1101).is_err());
+        // Fixed PSMs are accepted regardless of the dynamic-range parity rules.
+        assert!(Psm::new_checked(Psm::RFCOMM.into()).is_ok());
+    }
+
+    #[test]
+    fn test_psm_is_fixed() {
+        assert!(Psm::SDP.is_fixed());
+        assert!(Psm::RFCOMM.is_fixed());
+        assert!(Psm::AVCTP.is_fixed());
+        assert!(Psm::AVDTP.is_fixed());
+        assert!(!Psm::new(0x1001).is_fixed());
+    }
+
+    #[test]
+    fn test_service_definition_invalid_psms() {
+        let def = ServiceDefinition {
+            service_class_uuids: vec![Uuid::new16(0x1101)],
+            protocol_descriptor_list: vec![ProtocolDescriptor {
+                protocol: fidl_bredr::ProtocolIdentifier::L2Cap,
+                params: vec![DataElement::Uint16(0x1002)], // even low byte: invalid.
+            }],
+            additional_protocol_descriptor_lists: vec![],
+            profile_descriptors: vec![],
+            information: vec![],
+            additional_attributes: vec![],
+        };
+
+        let mut expected = HashSet::new();
+        expected.insert(Psm::new(0x1002));
+        assert_eq!(def.invalid_psms(), expected);
+    }
+
+    fn empty_service_definition(uuid: u16) -> ServiceDefinition {
+        ServiceDefinition {
+            service_class_uuids: vec![Uuid::new16(uuid)],
+            protocol_descriptor_list: vec![],
+            additional_protocol_descriptor_lists: vec![],
+            profile_descriptors: vec![],
+            information: vec![],
+            additional_attributes: vec![],
+        }
+    }
+
+    #[test]
+    fn test_service_definition_merge_unions_service_class_uuids() {
+        let a = empty_service_definition(0x1101);
+        let b = empty_service_definition(0x1102);
+
+        let merged = a.merge(&b).expect("should merge");
+        assert_eq!(merged.service_class_uuids, vec![Uuid::new16(0x1101), Uuid::new16(0x1102)]);
+
+        // Merging with an identical UUID doesn't duplicate it.
+        let merged_again = merged.merge(&empty_service_definition(0x1101)).expect("should merge");
+        assert_eq!(merged_again.service_class_uuids.len(), 2);
+    }
+
+    #[test]
+    fn test_service_definition_merge_concatenates_additional_lists_and_attributes() {
+        let mut a = empty_service_definition(0x1101);
+        a.additional_protocol_descriptor_lists.push(vec![ProtocolDescriptor {
+            protocol: fidl_bredr::ProtocolIdentifier::L2Cap,
+            params: vec![DataElement::Uint16(1)],
+        }]);
+        a.additional_attributes.push(Attribute { id: 1, element: DataElement::Uint8(1) });
+
+        let mut b = empty_service_definition(0x1101);
+        b.additional_protocol_descriptor_lists.push(vec![ProtocolDescriptor {
+            protocol: fidl_bredr::ProtocolIdentifier::L2Cap,
+            params: vec![DataElement::Uint16(2)],
+        }]);
+        b.additional_attributes.push(Attribute { id: 2, element: DataElement::Uint8(2) });
+
+        let merged = a.merge(&b).expect("should merge");
+        assert_eq!(merged.additional_protocol_descriptor_lists.len(), 2);
+        assert_eq!(merged.additional_attributes.len(), 2);
+    }
+
+    #[test]
+    fn test_service_definition_merge_rejects_conflicting_attributes() {
+        let mut a = empty_service_definition(0x1101);
+        a.additional_attributes.push(Attribute { id: 1, element: DataElement::Uint8(1) });
+
+        let mut b = empty_service_definition(0x1101);
+        b.additional_attributes.push(Attribute { id: 1, element: DataElement::Uint8(2) });
+
+        assert!(a.merge(&b).is_err());
+    }
+
+    #[test]
+    fn test_service_definition_merge_information_by_language() {
+        let info_en = Information {
+            language: "en".to_string(),
+            name: Some("Name".to_string()),
+            description: None,
+            provider: None,
+        };
+        let info_fr = Information {
+            language: "fr".to_string(),
+            name: Some("Nom".to_string()),
+            description: None,
+            provider: None,
+        };
+
+        let mut a = empty_service_definition(0x1101);
+        a.information.push(info_en.clone());
+        let mut b = empty_service_definition(0x1101);
+        b.information.push(info_fr.clone());
+
+        let merged = a.merge(&b).expect("should merge");
+        assert_eq!(merged.information, vec![info_en.clone(), info_fr]);
+
+        // Conflicting information for the same language is rejected.
+        let mut c = empty_service_definition(0x1101);
+        c.information.push(Information {
+            language: "en".to_string(),
+            name: Some("Different Name".to_string()),
+            description: None,
+            provider: None,
+        });
+        assert!(merged.merge(&c).is_err());
+    }
+
+    #[test]
+    fn test_service_definition_merge_keeps_higher_profile_version() {
+        let mut a = empty_service_definition(0x1101);
+        a.profile_descriptors.push(ProfileDescriptor {
+            profile_id: fidl_bredr::ServiceClassProfileIdentifier::SerialPort,
+            major_version: 1,
+            minor_version: 0,
+        });
+
+        let mut b = empty_service_definition(0x1101);
+        b.profile_descriptors.push(ProfileDescriptor {
+            profile_id: fidl_bredr::ServiceClassProfileIdentifier::SerialPort,
+            major_version: 1,
+            minor_version: 2,
+        });
+
+        let merged = a.merge(&b).expect("should merge");
+        assert_eq!(merged.profile_descriptors.len(), 1);
+        assert_eq!(merged.profile_descriptors[0].minor_version, 2);
     }
 
     #[test]
@@ -717,7 +1742,7 @@ mod tests {
             protocol: fidl_bredr::ProtocolIdentifier::L2Cap,
             params: vec![DataElement::Uint16(psm)],
         }];
-        assert_eq!(Some(psm), psm_from_protocol(&valid_psm));
+        assert_eq!(Some(Psm::new(psm)), psm_from_protocol(&valid_psm));
 
         let rfcomm = vec![
             ProtocolDescriptor {
@@ -730,6 +1755,173 @@ mod tests {
             },
         ];
         assert_eq!(None, psm_from_protocol(&rfcomm));
+        assert!(is_rfcomm_protocol(&rfcomm));
+        assert_eq!(Some(ServerChannel::new(10).unwrap()), server_channel_from_protocol(&rfcomm));
+        assert!(!is_rfcomm_protocol(&valid_psm));
+        assert_eq!(None, server_channel_from_protocol(&valid_psm));
+    }
+
+    #[test]
+    fn test_server_channel_validates_range() {
+        assert!(ServerChannel::new(0).is_none());
+        assert!(ServerChannel::new(31).is_none());
+        assert!(ServerChannel::new(1).is_some());
+        assert!(ServerChannel::new(30).is_some());
+    }
+
+    #[test]
+    fn test_service_definition_server_channel() {
+        let mut def = ServiceDefinition {
+            service_class_uuids: vec![Uuid::new16(0x1101)],
+            protocol_descriptor_list: vec![
+                ProtocolDescriptor {
+                    protocol: fidl_bredr::ProtocolIdentifier::L2Cap,
+                    params: vec![],
+                },
+                ProtocolDescriptor {
+                    protocol: fidl_bredr::ProtocolIdentifier::Rfcomm,
+                    params: vec![DataElement::Uint8(1)],
+                },
+            ],
+            additional_protocol_descriptor_lists: vec![],
+            profile_descriptors: vec![],
+            information: vec![],
+            additional_attributes: vec![],
+        };
+
+        assert_eq!(def.server_channel(), ServerChannel::new(1));
+
+        def.set_server_channel(ServerChannel::new(5).unwrap());
+        assert_eq!(def.server_channel(), ServerChannel::new(5));
+    }
+
+    #[test]
+    fn test_service_definition_rfcomm_channel_falls_back_to_additional_protocol() {
+        let def = ServiceDefinition {
+            service_class_uuids: vec![Uuid::new16(0x1101)],
+            protocol_descriptor_list: vec![],
+            additional_protocol_descriptor_lists: vec![vec![
+                ProtocolDescriptor {
+                    protocol: fidl_bredr::ProtocolIdentifier::L2Cap,
+                    params: vec![],
+                },
+                ProtocolDescriptor {
+                    protocol: fidl_bredr::ProtocolIdentifier::Rfcomm,
+                    params: vec![DataElement::Uint8(7)],
+                },
+            ]],
+            profile_descriptors: vec![],
+            information: vec![],
+            additional_attributes: vec![],
+        };
+
+        assert_eq!(def.server_channel(), None);
+        assert_eq!(def.rfcomm_channel(), ServerChannel::new(7));
+    }
+
+    #[test]
+    fn test_service_definition_supported_features() {
+        const ATTR_SUPPORTED_FEATURES: u16 = 0x0311;
+
+        let mut def = ServiceDefinition {
+            service_class_uuids: vec![Uuid::new16(0x1101)],
+            protocol_descriptor_list: vec![],
+            additional_protocol_descriptor_lists: vec![],
+            profile_descriptors: vec![],
+            information: vec![],
+            additional_attributes: vec![],
+        };
+
+        assert_eq!(def.attribute(ATTR_SUPPORTED_FEATURES), None);
+        assert_eq!(def.supported_features(ATTR_SUPPORTED_FEATURES), None);
+
+        def.additional_attributes.push(Attribute {
+            id: ATTR_SUPPORTED_FEATURES,
+            element: DataElement::Uint16(0x0001),
+        });
+        assert_eq!(def.supported_features(ATTR_SUPPORTED_FEATURES), Some(0x0001));
+
+        // Wrong element type for the attribute is treated as absent.
+        def.additional_attributes[0].element = DataElement::Str("not a bitfield".to_string());
+        assert_eq!(def.supported_features(ATTR_SUPPORTED_FEATURES), None);
+    }
+
+    #[test]
+    fn test_service_definition_builder_requires_service_class_uuid() {
+        let result = ServiceDefinitionBuilder::new().psm(Psm::new(0x0003)).build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_service_definition_builder_psm() {
+        let def = ServiceDefinitionBuilder::new()
+            .service_class_uuid(Uuid::new16(0x1101))
+            .psm(Psm::new(0x0003))
+            .build()
+            .expect("valid service definition");
+
+        assert_eq!(def.primary_psm(), Some(Psm::new(0x0003)));
+    }
+
+    #[test]
+    fn test_service_definition_builder_rfcomm() {
+        let def = ServiceDefinitionBuilder::new()
+            .service_class_uuid(Uuid::new16(0x1101))
+            .rfcomm(ServerChannel::new(5).unwrap())
+            .build()
+            .expect("valid service definition");
+
+        assert_eq!(def.server_channel(), Some(ServerChannel::new(5).unwrap()));
+    }
+
+    #[test]
+    fn test_service_definition_builder_rfcomm_missing_channel_is_rejected() {
+        // A hand-built protocol list that looks like RFCOMM but carries no channel parameter
+        // should be rejected by `build`, not just by the builder's own `rfcomm` method.
+        let mut builder = ServiceDefinitionBuilder::new().service_class_uuid(Uuid::new16(0x1101));
+        builder.protocol_descriptor_list = vec![
+            ProtocolDescriptor { protocol: fidl_bredr::ProtocolIdentifier::L2Cap, params: vec![] },
+            ProtocolDescriptor {
+                protocol: fidl_bredr::ProtocolIdentifier::Rfcomm,
+                params: vec![],
+            },
+        ];
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn test_service_definition_builder_profile_and_information() {
+        let info = Information {
+            language: "en".to_string(),
+            name: Some("Test".to_string()),
+            description: None,
+            provider: None,
+        };
+        let def = ServiceDefinitionBuilder::new()
+            .service_class_uuid(Uuid::new16(0x1101))
+            .psm(Psm::new(0x0003))
+            .profile(fidl_bredr::ServiceClassProfileIdentifier::SerialPort, (1, 2))
+            .information(info.clone())
+            .build()
+            .expect("valid service definition");
+
+        assert_eq!(def.profile_descriptors.len(), 1);
+        assert_eq!(def.profile_descriptors[0].major_version, 1);
+        assert_eq!(def.profile_descriptors[0].minor_version, 2);
+        assert_eq!(def.information, vec![info]);
+    }
+
+    #[test]
+    fn test_standard_service_definitions_build() {
+        pbap_pse_service_definition(ServerChannel::new(1).unwrap(), (1, 2), None)
+            .expect("pbap record should build");
+        map_mas_service_definition(ServerChannel::new(2).unwrap(), (1, 3), None)
+            .expect("map record should build");
+        opp_service_definition(ServerChannel::new(3).unwrap(), (1, 1), None)
+            .expect("opp record should build");
+        sap_service_definition(ServerChannel::new(4).unwrap(), (1, 0), None)
+            .expect("sap record should build");
+        dip_service_definition((1, 0), None).expect("dip record should build");
     }
 
     #[test]
@@ -831,8 +2023,8 @@ mod tests {
         }];
 
         let mut expected_psms = HashSet::new();
-        expected_psms.insert(psm1);
-        assert_eq!(def.primary_psm(), Some(psm1));
+        expected_psms.insert(Psm::new(psm1));
+        assert_eq!(def.primary_psm(), Some(Psm::new(psm1)));
         assert_eq!(def.additional_psms(), HashSet::new());
         assert_eq!(def.psm_set(), expected_psms);
 
@@ -848,10 +2040,10 @@ mod tests {
         ];
 
         let mut expected_psms = HashSet::new();
-        expected_psms.insert(psm2);
-        assert_eq!(def.primary_psm(), Some(psm1));
+        expected_psms.insert(Psm::new(psm2));
+        assert_eq!(def.primary_psm(), Some(Psm::new(psm1)));
         assert_eq!(def.additional_psms(), expected_psms);
-        expected_psms.insert(psm1);
+        expected_psms.insert(Psm::new(psm1));
         assert_eq!(def.psm_set(), expected_psms);
     }
 
@@ -963,13 +2155,22 @@ mod tests {
     fn test_channel_parameters_conversions() {
         let channel_mode = Some(fidl_bredr::ChannelMode::EnhancedRetransmission);
         let max_rx_sdu_size = Some(MIN_RX_SDU_SIZE);
+        let flush_timeout = Some(zx::Duration::from_millis(100));
+        let acl_priority = Some(fidl_bredr::AclPriority::Source);
 
-        let local =
-            ChannelParameters { channel_mode, max_rx_sdu_size, security_requirements: None };
+        let local = ChannelParameters {
+            channel_mode,
+            max_rx_sdu_size,
+            security_requirements: None,
+            flush_timeout,
+            acl_priority,
+        };
         let fidl = fidl_bredr::ChannelParameters {
             channel_mode,
             max_rx_sdu_size,
             security_requirements: None,
+            flush_timeout: flush_timeout.map(|t| t.into_nanos()),
+            acl_priority,
             ..fidl_bredr::ChannelParameters::EMPTY
         };
 
@@ -986,6 +2187,8 @@ mod tests {
             channel_mode: None,
             max_rx_sdu_size: None,
             security_requirements: None,
+            flush_timeout: None,
+            acl_priority: None,
         };
 
         let fidl_to_local = ChannelParameters::try_from(&fidl).expect("conversion should work");
@@ -999,6 +2202,8 @@ mod tests {
             channel_mode: None,
             max_rx_sdu_size: too_small_sdu,
             security_requirements: None,
+            flush_timeout: None,
+            acl_priority: None,
         };
         let fidl = fidl_bredr::ChannelParameters {
             channel_mode: None,
@@ -1092,47 +2297,139 @@ mod tests {
         assert_eq!(combine_security_requirements(&req1, &req2), expected);
     }
 
+    #[test]
+    fn test_data_element_typed_conversions() {
+        assert_eq!(u16::try_from(&DataElement::Uint16(10)), Ok(10));
+        assert_eq!(i32::try_from(&DataElement::Int32(-5)), Ok(-5));
+        assert_eq!(bool::try_from(&DataElement::Bool(true)), Ok(true));
+        assert_eq!(String::try_from(&DataElement::Str("hello".to_string())), Ok("hello".to_string()));
+        assert_eq!(String::try_from(&DataElement::Url("hello".to_string())), Ok("hello".to_string()));
+
+        let uuid = Uuid::new16(0x1101);
+        let elem = DataElement::Uuid(uuid.into());
+        assert_eq!(Uuid::try_from(&elem), Ok(uuid));
+
+        assert!(u16::try_from(&DataElement::Str("nope".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_data_element_as_sequence() {
+        let seq = DataElement::Sequence(vec![Box::new(DataElement::Uint8(1))]);
+        let elems = seq.as_sequence().expect("should be a sequence");
+        assert_eq!(elems.len(), 1);
+
+        let alts = DataElement::Alternatives(vec![Box::new(DataElement::Uint8(1))]);
+        assert!(alts.as_sequence().is_ok());
+
+        let not_a_seq = DataElement::Uint8(1);
+        assert!(not_a_seq.as_sequence().is_err());
+
+        let flattened: Vec<DataElement> = Vec::try_from(&seq).expect("should flatten");
+        assert_eq!(flattened, vec![DataElement::Uint8(1)]);
+    }
+
+    #[test]
+    fn test_data_element_byte_round_trip() {
+        let cases = vec![
+            DataElement::Uint8(0xAB),
+            DataElement::Uint16(0xABCD),
+            DataElement::Uint32(0xDEADC0DE),
+            DataElement::Uint64(0x1122334455667788),
+            DataElement::Int8(-5),
+            DataElement::Int16(-1000),
+            DataElement::Int32(-100000),
+            DataElement::Int64(-1),
+            DataElement::Bool(true),
+            DataElement::Str("hello sdp".to_string()),
+            DataElement::Url("https://fuchsia.dev".to_string()),
+            DataElement::Uuid(Uuid::new16(0x1101).into()),
+            DataElement::Uuid(Uuid::new32(0xDEADBEEF).into()),
+            DataElement::Sequence(vec![
+                Box::new(DataElement::Uint8(1)),
+                Box::new(DataElement::Str("nested".to_string())),
+            ]),
+            DataElement::Alternatives(vec![Box::new(DataElement::Bool(false))]),
+        ];
+
+        for elem in cases {
+            let bytes = elem.encode();
+            let (decoded, consumed) = DataElement::decode(&bytes).expect("should decode");
+            assert_eq!(consumed, bytes.len());
+            assert_eq!(decoded, elem);
+        }
+    }
+
+    #[test]
+    fn test_data_element_uuid_uses_short_form() {
+        let short = DataElement::Uuid(Uuid::new16(0x1101).into());
+        assert_eq!(short.encode().len(), 3); // 1-byte header + 2-byte payload.
+
+        let long = DataElement::Uuid(fidl_bt::Uuid {
+            value: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+        });
+        assert_eq!(long.encode().len(), 17); // 1-byte header + 16-byte payload.
+    }
+
+    #[test]
+    fn test_data_element_decode_rejects_truncated_buffers() {
+        assert!(DataElement::decode(&[]).is_err());
+
+        // Claims a 2-byte length field but the buffer ends right after.
+        let header = (4u8 << 3) | 6;
+        assert!(DataElement::decode(&[header, 0x00]).is_err());
+
+        // Declares a longer payload than is actually present.
+        let header = (1u8 << 3) | 1;
+        assert!(DataElement::decode(&[header, 0x00]).is_err());
+    }
+
     #[test]
     fn test_combine_channel_parameters() {
         let p1 = ChannelParameters::default();
         let p2 = ChannelParameters::default();
         let expected = ChannelParameters::default();
-        assert_eq!(combine_channel_parameters(&p1, &p2), expected);
+        assert_eq!(combine_channel_parameters(&p1, &p2).unwrap(), expected);
 
         let p1 = ChannelParameters {
             channel_mode: Some(fidl_bredr::ChannelMode::EnhancedRetransmission),
             max_rx_sdu_size: None,
             security_requirements: None,
+            ..Default::default()
         };
         let p2 = ChannelParameters {
             channel_mode: Some(fidl_bredr::ChannelMode::Basic),
             max_rx_sdu_size: Some(70),
             security_requirements: None,
+            ..Default::default()
         };
         let expected = ChannelParameters {
             channel_mode: Some(fidl_bredr::ChannelMode::Basic),
             max_rx_sdu_size: Some(70),
             security_requirements: None,
+            ..Default::default()
         };
-        assert_eq!(combine_channel_parameters(&p1, &p2), expected);
+        assert_eq!(combine_channel_parameters(&p1, &p2).unwrap(), expected);
 
         let empty_seq_reqs = SecurityRequirements::default();
         let p1 = ChannelParameters {
             channel_mode: None,
             max_rx_sdu_size: Some(75),
             security_requirements: Some(empty_seq_reqs.clone()),
+            ..Default::default()
         };
         let p2 = ChannelParameters {
             channel_mode: Some(fidl_bredr::ChannelMode::EnhancedRetransmission),
             max_rx_sdu_size: None,
             security_requirements: None,
+            ..Default::default()
         };
         let expected = ChannelParameters {
             channel_mode: Some(fidl_bredr::ChannelMode::EnhancedRetransmission),
             max_rx_sdu_size: Some(75),
             security_requirements: Some(empty_seq_reqs),
+            ..Default::default()
         };
-        assert_eq!(combine_channel_parameters(&p1, &p2), expected);
+        assert_eq!(combine_channel_parameters(&p1, &p2).unwrap(), expected);
 
         let reqs1 = SecurityRequirements {
             authentication_required: Some(true),
@@ -1147,17 +2444,45 @@ mod tests {
             channel_mode: None,
             max_rx_sdu_size: Some(90),
             security_requirements: Some(reqs1),
+            ..Default::default()
         };
         let p2 = ChannelParameters {
             channel_mode: Some(fidl_bredr::ChannelMode::Basic),
             max_rx_sdu_size: Some(70),
             security_requirements: Some(reqs2),
+            ..Default::default()
         };
         let expected = ChannelParameters {
             channel_mode: Some(fidl_bredr::ChannelMode::Basic),
             max_rx_sdu_size: Some(70),
             security_requirements: Some(combined_reqs),
+            ..Default::default()
+        };
+        assert_eq!(combine_channel_parameters(&p1, &p2).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_combine_channel_parameters_flush_timeout_and_priority() {
+        let p1 = ChannelParameters {
+            flush_timeout: Some(zx::Duration::from_millis(200)),
+            acl_priority: Some(fidl_bredr::AclPriority::Normal),
+            ..Default::default()
+        };
+        let p2 = ChannelParameters {
+            flush_timeout: Some(zx::Duration::from_millis(100)),
+            acl_priority: Some(fidl_bredr::AclPriority::Source),
+            ..Default::default()
         };
-        assert_eq!(combine_channel_parameters(&p1, &p2), expected);
+        let combined = combine_channel_parameters(&p1, &p2).unwrap();
+        // The shorter (more aggressive) flush timeout wins.
+        assert_eq!(combined.flush_timeout, Some(zx::Duration::from_millis(100)));
+        // A non-Normal priority wins over Normal.
+        assert_eq!(combined.acl_priority, Some(fidl_bredr::AclPriority::Source));
+
+        let p1 =
+            ChannelParameters { acl_priority: Some(fidl_bredr::AclPriority::Source), ..Default::default() };
+        let p2 =
+            ChannelParameters { acl_priority: Some(fidl_bredr::AclPriority::Sink), ..Default::default() };
+        assert!(combine_channel_parameters(&p1, &p2).is_err());
     }
 }
@@ -2,6 +2,7 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
+use anyhow::{format_err, Error};
 use fidl_fuchsia_wlan_common as fidl_common;
 use fidl_fuchsia_wlan_internal as fidl_internal;
 use fidl_fuchsia_wlan_mlme::{self as fidl_mlme, BandCapabilities};
@@ -108,3 +109,221 @@ pub fn clone_scan_request(sr: &fidl_mlme::ScanRequest) -> fidl_mlme::ScanRequest
         ..*sr
     }
 }
+
+/// The capabilities a client should advertise when associating to a specific BSS: the
+/// intersection of what this device supports and what the BSS itself advertises, as computed by
+/// `derive_sta_capabilities`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaCapabilities {
+    pub rates: Vec<u8>,
+    pub ht_cap: Option<Box<fidl_internal::HtCapabilities>>,
+    pub vht_cap: Option<Box<fidl_internal::VhtCapabilities>>,
+}
+
+/// Negotiates the HT Capabilities this device and `bss` have in common. Every bit of the HT
+/// Capability Info field (bytes 0-1) is an independent yes/no capability, so ANDing the two
+/// fields together is exactly the field-wise minimum - including the Supported Channel Width Set
+/// and Max A-MSDU Length bits. The Max A-MPDU Length Exponent (bits 0-1 of byte 2) is a 2-bit
+/// number rather than independent flags, so it is negotiated with a numeric min instead. The Rx
+/// MCS bitmask (the first 10 bytes of the Supported MCS Set, bytes 3-12) is ANDed so the
+/// negotiated set never claims support for an MCS either side lacks.
+fn negotiate_ht_capabilities(
+    device: &fidl_internal::HtCapabilities,
+    bss: &fidl_internal::HtCapabilities,
+) -> fidl_internal::HtCapabilities {
+    let mut bytes = device.bytes;
+
+    bytes[0] &= bss.bytes[0];
+    bytes[1] &= bss.bytes[1];
+
+    let device_ampdu_factor = bytes[2] & 0b11;
+    let bss_ampdu_factor = bss.bytes[2] & 0b11;
+    bytes[2] = (bytes[2] & !0b11) | device_ampdu_factor.min(bss_ampdu_factor);
+
+    for i in 3..13 {
+        bytes[i] &= bss.bytes[i];
+    }
+
+    fidl_internal::HtCapabilities { bytes }
+}
+
+/// Negotiates the VHT Capabilities this device and `bss` have in common. The Supported Channel
+/// Width Set (bits 2-3 of the VHT Capabilities Info field, bytes 0-3) is a numeric capability
+/// level like the HT A-MPDU factor above, so it is negotiated with a numeric min. The Rx MCS Map
+/// (the first two bytes of the VHT-MCS and NSS Set, bytes 4-5) records, per spatial stream, a
+/// 2-bit highest-supported-MCS code (3 meaning the stream is unsupported); each stream takes the
+/// lower code, or 3 if either side lacks the stream, so the negotiated set never claims support
+/// for an MCS/NSS combination either side lacks.
+fn negotiate_vht_capabilities(
+    device: &fidl_internal::VhtCapabilities,
+    bss: &fidl_internal::VhtCapabilities,
+) -> fidl_internal::VhtCapabilities {
+    let mut bytes = device.bytes;
+
+    const CHAN_WIDTH_SHIFT: u32 = 2;
+    const CHAN_WIDTH_MASK: u32 = 0b11 << CHAN_WIDTH_SHIFT;
+    let info = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let bss_info = u32::from_le_bytes([bss.bytes[0], bss.bytes[1], bss.bytes[2], bss.bytes[3]]);
+    let device_width = (info & CHAN_WIDTH_MASK) >> CHAN_WIDTH_SHIFT;
+    let bss_width = (bss_info & CHAN_WIDTH_MASK) >> CHAN_WIDTH_SHIFT;
+    let negotiated_width = device_width.min(bss_width);
+    bytes[0..4].copy_from_slice(
+        &((info & !CHAN_WIDTH_MASK) | (negotiated_width << CHAN_WIDTH_SHIFT)).to_le_bytes(),
+    );
+
+    let device_map = u16::from_le_bytes([bytes[4], bytes[5]]);
+    let bss_map = u16::from_le_bytes([bss.bytes[4], bss.bytes[5]]);
+    let mut map = 0u16;
+    for shift in (0..16).step_by(2) {
+        let device_mcs = (device_map >> shift) & 0b11;
+        let bss_mcs = (bss_map >> shift) & 0b11;
+        let mcs = if device_mcs == 0b11 || bss_mcs == 0b11 { 0b11 } else { device_mcs.min(bss_mcs) };
+        map |= mcs << shift;
+    }
+    bytes[4..6].copy_from_slice(&map.to_le_bytes());
+
+    fidl_internal::VhtCapabilities { bytes }
+}
+
+/// Computes the capabilities this device should advertise when associating to `bss`: the band
+/// whose channels cover `bss.chan.primary`, intersected with whatever `bss` itself advertises.
+pub fn derive_sta_capabilities(
+    device_info: &fidl_mlme::DeviceInfo,
+    bss: &fidl_internal::BssDescription,
+) -> Result<StaCapabilities, Error> {
+    let band = device_info
+        .bands
+        .iter()
+        .find(|band| band.channels.contains(&bss.chan.primary))
+        .ok_or_else(|| {
+            format_err!(
+                "device has no band capabilities covering BSS primary channel {}",
+                bss.chan.primary
+            )
+        })?;
+
+    let rates = band.rates.iter().filter(|rate| bss.rates.contains(rate)).cloned().collect();
+
+    let ht_cap = match (band.ht_cap.as_deref(), bss.ht_cap.as_deref()) {
+        (Some(device_ht), Some(bss_ht)) => {
+            Some(Box::new(negotiate_ht_capabilities(device_ht, bss_ht)))
+        }
+        _ => None,
+    };
+
+    let vht_cap = match (band.vht_cap.as_deref(), bss.vht_cap.as_deref()) {
+        (Some(device_vht), Some(bss_vht)) => {
+            Some(Box::new(negotiate_vht_capabilities(device_vht, bss_vht)))
+        }
+        _ => None,
+    };
+
+    Ok(StaCapabilities { rates, ht_cap, vht_cap })
+}
+
+/// Builds a `fidl_internal::BssDescription` starting from sane infrastructure-BSS defaults, for
+/// tests and code that synthesizes scan results. Callers only need to override the fields a given
+/// test cares about rather than filling in every field of the (rather large) FIDL struct by hand.
+pub struct BssDescriptionBuilder {
+    bssid: [u8; 6],
+    ssid: Vec<u8>,
+    cap: u16,
+    rates: Vec<u8>,
+    rsne: Option<Vec<u8>>,
+    chan: fidl_common::WlanChan,
+    rssi_dbm: i8,
+}
+
+/// The Privacy bit in the 802.11 Capability Information field.
+const CAP_PRIVACY_BIT: u16 = 1 << 4;
+
+impl Default for BssDescriptionBuilder {
+    fn default() -> Self {
+        Self {
+            bssid: [0u8; 6],
+            ssid: vec![],
+            cap: 0,
+            rates: vec![],
+            rsne: None,
+            chan: fidl_common::WlanChan {
+                primary: 1,
+                cbw: fidl_common::Cbw::Cbw20,
+                secondary80: 0,
+            },
+            rssi_dbm: 0,
+        }
+    }
+}
+
+impl BssDescriptionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bssid(mut self, bssid: [u8; 6]) -> Self {
+        self.bssid = bssid;
+        self
+    }
+
+    pub fn ssid(mut self, ssid: Vec<u8>) -> Self {
+        self.ssid = ssid;
+        self
+    }
+
+    /// Toggles the Privacy bit in the Capability Information field and attaches (or removes) a
+    /// placeholder RSNE to match.
+    pub fn with_privacy(mut self, privacy: bool) -> Self {
+        if privacy {
+            self.cap |= CAP_PRIVACY_BIT;
+            self.rsne = Some(vec![0x30, 0x00]);
+        } else {
+            self.cap &= !CAP_PRIVACY_BIT;
+            self.rsne = None;
+        }
+        self
+    }
+
+    pub fn rates(mut self, rates: Vec<u8>) -> Self {
+        self.rates = rates;
+        self
+    }
+
+    pub fn channel(mut self, chan: fidl_common::WlanChan) -> Self {
+        self.chan = chan;
+        self
+    }
+
+    pub fn rssi_dbm(mut self, rssi_dbm: i8) -> Self {
+        self.rssi_dbm = rssi_dbm;
+        self
+    }
+
+    pub fn build(self) -> fidl_internal::BssDescription {
+        fidl_internal::BssDescription {
+            bssid: self.bssid,
+            ssid: self.ssid,
+            bss_type: fidl_internal::BssTypes::Infrastructure,
+            beacon_period: 100,
+            dtim_period: 100,
+            timestamp: 0,
+            local_time: 0,
+
+            cap: self.cap,
+            rates: self.rates,
+            country: vec![],
+
+            rsne: self.rsne,
+            vendor_ies: vec![],
+
+            ht_cap: Some(Box::new(fidl_internal::HtCapabilities { bytes: [0u8; 26] })),
+            ht_op: Some(Box::new(fidl_internal::HtOperation { bytes: [0u8; 22] })),
+
+            vht_cap: Some(Box::new(fidl_internal::VhtCapabilities { bytes: [0u8; 12] })),
+            vht_op: Some(Box::new(fidl_internal::VhtOperation { bytes: [0u8; 5] })),
+
+            chan: self.chan,
+            rssi_dbm: self.rssi_dbm,
+            snr_db: 0,
+        }
+    }
+}
@@ -6,14 +6,16 @@
 use {
     crate::{
         client::types, mode_management::iface_manager_api::IfaceManagerApi,
-        util::sme_conversion::security_from_sme_protection,
+        util::clone::clone_bss_info, util::sme_conversion::security_from_sme_protection,
     },
     anyhow::{format_err, Error},
     async_trait::async_trait,
     fidl_fuchsia_location_sensor as fidl_location_sensor, fidl_fuchsia_wlan_policy as fidl_policy,
     fidl_fuchsia_wlan_sme as fidl_sme,
+    fuchsia_async as fasync,
     fuchsia_component::client::connect_to_service,
-    futures::{lock::Mutex, prelude::*},
+    fuchsia_zircon as zx,
+    futures::{channel::oneshot, lock::Mutex, prelude::*},
     log::{debug, error, info},
     std::{collections::HashMap, sync::Arc},
     stream::FuturesUnordered,
@@ -22,6 +24,41 @@ use {
 // Arbitrary count of networks (ssid/security pairs) to output per request
 const OUTPUT_CHUNK_NETWORK_COUNT: usize = 5;
 
+// How long a cached scan result remains valid before a fresh SME scan is required.
+const SCAN_RESULT_CACHE_TTL: zx::Duration = zx::Duration::from_seconds(15);
+
+/// Caches the most recently merged scan results so that rapidly repeated callers (UI polling, the
+/// network selector, and the location sensor all asking in close succession) don't each force a
+/// fresh over-the-air SME scan. A cached result only satisfies a caller whose requested scan type
+/// it covers: an entry built from a passive-only scan cannot satisfy a caller that additionally
+/// needs active-scan data for specific SSIDs it hasn't already covered.
+pub struct ScanResultCache {
+    bss_by_network: HashMap<fidl_policy::NetworkIdentifier, Vec<types::Bss>>,
+    as_of: zx::Time,
+    active_scan_ssids: Vec<Vec<u8>>,
+}
+
+impl Default for ScanResultCache {
+    fn default() -> Self {
+        Self {
+            bss_by_network: HashMap::new(),
+            as_of: zx::Time::from_nanos(0),
+            active_scan_ssids: vec![],
+        }
+    }
+}
+
+impl ScanResultCache {
+    /// True if this entry is still within `SCAN_RESULT_CACHE_TTL` and was built with active scan
+    /// results covering every SSID in `requested_active_scan_ssids`.
+    fn is_valid_for(&self, requested_active_scan_ssids: &[Vec<u8>]) -> bool {
+        if zx::Time::get_monotonic() - self.as_of > SCAN_RESULT_CACHE_TTL {
+            return false;
+        }
+        requested_active_scan_ssids.iter().all(|ssid| self.active_scan_ssids.contains(ssid))
+    }
+}
+
 /// Allows for consumption of updated scan results.
 #[async_trait]
 pub trait ScanResultUpdate: Sync + Send {
@@ -66,13 +103,131 @@ async fn sme_scan(
     Err(())
 }
 
+fn clone_scan_result(
+    result: &Result<Vec<fidl_sme::BssInfo>, ()>,
+) -> Result<Vec<fidl_sme::BssInfo>, ()> {
+    result.as_ref().map(|aps| aps.iter().map(clone_bss_info).collect()).map_err(|e| *e)
+}
+
+struct PendingActiveScan {
+    ssids: Vec<Vec<u8>>,
+    channels: Vec<u8>,
+    senders: Vec<oneshot::Sender<Result<Vec<fidl_sme::BssInfo>, ()>>>,
+}
+
+/// Coalesces concurrent scan requests into a single SME transaction, modeled after the SME-side
+/// `ScanScheduler`'s DiscoveryScan/JoinScan queuing. While a scan is in flight, new requesters
+/// compatible with it (a passive scan waiting on another passive scan; an active scan for SSID X
+/// waiting on one that hasn't dispatched yet, unioning X into its SSID list) ride along and
+/// receive the same `Vec<fidl_sme::BssInfo>` instead of each driving their own over-the-air scan.
+/// Share one `ScanScheduler` across concurrent callers to get this coalescing; construct a fresh
+/// one per call to opt out.
+pub struct ScanScheduler {
+    iface_manager: Arc<Mutex<dyn IfaceManagerApi + Send>>,
+    pending_passive: Mutex<Option<Vec<oneshot::Sender<Result<Vec<fidl_sme::BssInfo>, ()>>>>>,
+    pending_active: Mutex<Option<PendingActiveScan>>,
+}
+
+impl ScanScheduler {
+    pub fn new(iface_manager: Arc<Mutex<dyn IfaceManagerApi + Send>>) -> Arc<Self> {
+        Arc::new(Self {
+            iface_manager,
+            pending_passive: Mutex::new(None),
+            pending_active: Mutex::new(None),
+        })
+    }
+
+    /// Requests a passive scan, riding along on one already in flight if present.
+    pub async fn scan_passive(self: &Arc<Self>) -> Result<Vec<fidl_sme::BssInfo>, ()> {
+        let (sender, receiver) = oneshot::channel();
+        let mut pending = self.pending_passive.lock().await;
+        match pending.as_mut() {
+            Some(senders) => senders.push(sender),
+            None => {
+                *pending = Some(vec![sender]);
+                let scheduler = Arc::clone(self);
+                fasync::Task::spawn(async move { scheduler.dispatch_passive().await }).detach();
+            }
+        }
+        drop(pending);
+        receiver.await.unwrap_or(Err(()))
+    }
+
+    async fn dispatch_passive(self: Arc<Self>) {
+        let scan_request = fidl_sme::ScanRequest::Passive(fidl_sme::PassiveScanRequest {});
+        let result = sme_scan(Arc::clone(&self.iface_manager), scan_request).await;
+        // Taken only after the scan completes, so requesters that arrived while it was in flight
+        // share its result.
+        let senders = self.pending_passive.lock().await.take().unwrap_or_default();
+        for sender in senders {
+            let _ = sender.send(clone_scan_result(&result));
+        }
+    }
+
+    /// Requests an active scan for `ssids` restricted to `channels` (empty means all channels). If
+    /// an active scan hasn't dispatched to the SME yet, `ssids`/`channels` are unioned into it;
+    /// otherwise this request waits for its own scan.
+    pub async fn scan_active(
+        self: &Arc<Self>,
+        ssids: Vec<Vec<u8>>,
+        channels: Vec<u8>,
+    ) -> Result<Vec<fidl_sme::BssInfo>, ()> {
+        let (sender, receiver) = oneshot::channel();
+        let mut pending = self.pending_active.lock().await;
+        match pending.as_mut() {
+            Some(scan) => {
+                for ssid in ssids {
+                    if !scan.ssids.contains(&ssid) {
+                        scan.ssids.push(ssid);
+                    }
+                }
+                // An empty channel list means "scan all channels"; keep that if either side wants
+                // it, otherwise union the specific channels requested.
+                if scan.channels.is_empty() || channels.is_empty() {
+                    scan.channels = vec![];
+                } else {
+                    for channel in channels {
+                        if !scan.channels.contains(&channel) {
+                            scan.channels.push(channel);
+                        }
+                    }
+                }
+                scan.senders.push(sender);
+            }
+            None => {
+                *pending = Some(PendingActiveScan { ssids, channels, senders: vec![sender] });
+                let scheduler = Arc::clone(self);
+                fasync::Task::spawn(async move { scheduler.dispatch_active().await }).detach();
+            }
+        }
+        drop(pending);
+        receiver.await.unwrap_or(Err(()))
+    }
+
+    async fn dispatch_active(self: Arc<Self>) {
+        let scan = match self.pending_active.lock().await.take() {
+            Some(scan) => scan,
+            None => return,
+        };
+        let scan_request = fidl_sme::ScanRequest::Active(fidl_sme::ActiveScanRequest {
+            ssids: scan.ssids,
+            channels: scan.channels,
+        });
+        let result = sme_scan(Arc::clone(&self.iface_manager), scan_request).await;
+        for sender in scan.senders {
+            let _ = sender.send(clone_scan_result(&result));
+        }
+    }
+}
+
 /// Handles incoming scan requests by creating a new SME scan request.
 /// For the output_iterator, returns scan results and/or errors.
 /// On successful scan, also provides scan results to:
 /// - Emergency Location Provider
 /// - Network Selection Module
 pub(crate) async fn perform_scan<F>(
-    iface_manager: Arc<Mutex<dyn IfaceManagerApi + Send>>,
+    scan_scheduler: Arc<ScanScheduler>,
+    scan_result_cache: Arc<Mutex<ScanResultCache>>,
     mut output_iterator: Option<fidl::endpoints::ServerEnd<fidl_policy::ScanResultIteratorMarker>>,
     mut network_selector: impl ScanResultUpdate,
     mut location_sensor_updater: impl ScanResultUpdate,
@@ -80,53 +235,80 @@ pub(crate) async fn perform_scan<F>(
 ) where
     F: FnOnce(&Vec<types::ScanResult>) -> Option<Vec<Vec<u8>>>,
 {
-    let mut bss_by_network: HashMap<fidl_policy::NetworkIdentifier, Vec<types::Bss>> =
-        HashMap::new();
+    // A fresh cache entry already covering the active SSIDs we end up wanting lets us skip both
+    // SME scans entirely; a fresh entry covering none of them still lets us skip the passive scan.
+    let cached = {
+        let cache = scan_result_cache.lock().await;
+        if cache.is_valid_for(&[]) { Some(cache.bss_by_network.clone()) } else { None }
+    };
 
-    // Perform an initial passive scan
-    let scan_request = fidl_sme::ScanRequest::Passive(fidl_sme::PassiveScanRequest {});
-    let sme_result = sme_scan(Arc::clone(&iface_manager), scan_request).await;
-    match sme_result {
-        Ok(results) => {
-            insert_bss_to_network_bss_map(&mut bss_by_network, results, true);
-        }
-        Err(()) => {
-            // The passive scan failed. Send an error to the requester and return early.
-            if let Some(output_iterator) = output_iterator {
-                send_scan_error(output_iterator, fidl_policy::ScanErrorCode::GeneralError)
-                    .await
-                    .unwrap_or_else(|e| error!("Failed to send scan error: {}", e));
+    let mut bss_by_network: HashMap<fidl_policy::NetworkIdentifier, Vec<types::Bss>> =
+        match cached {
+            Some(bss_by_network) => bss_by_network,
+            None => {
+                // Perform an initial passive scan
+                let mut bss_by_network = HashMap::new();
+                let sme_result = scan_scheduler.scan_passive().await;
+                match sme_result {
+                    Ok(results) => {
+                        insert_bss_to_network_bss_map(&mut bss_by_network, results, true);
+                    }
+                    Err(()) => {
+                        // The passive scan failed. Send an error to the requester and return early.
+                        if let Some(output_iterator) = output_iterator {
+                            send_scan_error(output_iterator, fidl_policy::ScanErrorCode::GeneralError)
+                                .await
+                                .unwrap_or_else(|e| error!("Failed to send scan error: {}", e));
+                        }
+                        return;
+                    }
+                };
+                bss_by_network
             }
-            return;
-        }
-    };
+        };
 
     // Determine which active scans to perform by asking the active_scan_decider()
+    let mut newly_covered_active_ssids = vec![];
     if let Some(requested_active_scan_ssids) =
         active_scan_decider(&network_bss_map_to_scan_result(&bss_by_network))
     {
-        let scan_request = fidl_sme::ScanRequest::Active(fidl_sme::ActiveScanRequest {
-            ssids: requested_active_scan_ssids,
-            channels: vec![],
-        });
-        let sme_result = sme_scan(iface_manager, scan_request).await;
-        match sme_result {
-            Ok(results) => {
-                insert_bss_to_network_bss_map(&mut bss_by_network, results, false);
-            }
-            Err(()) => {
-                // There was an error in the active scan. For the FIDL interface, send an error. We
-                // `.take()` the output_iterator here, so it won't be used for sending results below.
-                if let Some(output_iterator) = output_iterator.take() {
-                    send_scan_error(output_iterator, fidl_policy::ScanErrorCode::GeneralError)
-                        .await
-                        .unwrap_or_else(|e| error!("Failed to send scan error: {}", e));
-                };
-                info!("Proceeding with passive scan results for non-FIDL scan consumers");
+        let already_cached =
+            scan_result_cache.lock().await.is_valid_for(&requested_active_scan_ssids);
+        if already_cached {
+            newly_covered_active_ssids = requested_active_scan_ssids;
+        } else {
+            let sme_result =
+                scan_scheduler.scan_active(requested_active_scan_ssids.clone(), vec![]).await;
+            match sme_result {
+                Ok(results) => {
+                    insert_bss_to_network_bss_map(&mut bss_by_network, results, false);
+                    newly_covered_active_ssids = requested_active_scan_ssids;
+                }
+                Err(()) => {
+                    // There was an error in the active scan. For the FIDL interface, send an error. We
+                    // `.take()` the output_iterator here, so it won't be used for sending results below.
+                    if let Some(output_iterator) = output_iterator.take() {
+                        send_scan_error(output_iterator, fidl_policy::ScanErrorCode::GeneralError)
+                            .await
+                            .unwrap_or_else(|e| error!("Failed to send scan error: {}", e));
+                    };
+                    info!("Proceeding with passive scan results for non-FIDL scan consumers");
+                }
             }
         }
     };
 
+    {
+        let mut cache = scan_result_cache.lock().await;
+        cache.bss_by_network = bss_by_network.clone();
+        cache.as_of = zx::Time::get_monotonic();
+        for ssid in newly_covered_active_ssids {
+            if !cache.active_scan_ssids.contains(&ssid) {
+                cache.active_scan_ssids.push(ssid);
+            }
+        }
+    }
+
     let scan_results = network_bss_map_to_scan_result(&bss_by_network);
     let mut scan_result_consumers = FuturesUnordered::new();
 
@@ -1003,8 +1185,14 @@ mod tests {
         // Issue request to scan.
         let (iter, iter_server) =
             fidl::endpoints::create_proxy().expect("failed to create iterator");
-        let scan_fut =
-            perform_scan(client, Some(iter_server), network_selector, location_sensor, |_| None);
+        let scan_fut = perform_scan(
+            ScanScheduler::new(client),
+            Arc::new(Mutex::new(ScanResultCache::default())),
+            Some(iter_server),
+            network_selector,
+            location_sensor,
+            |_| None,
+        );
         pin_mut!(scan_fut);
 
         // Request a chunk of scan results. Progress until waiting on response from server side of
@@ -1066,6 +1254,187 @@ mod tests {
         );
     }
 
+    #[test]
+    fn scan_result_cache_hit_skips_sme_scan() {
+        let mut exec = fasync::Executor::new().expect("failed to create an executor");
+        let (client, mut sme_stream) = exec.run_singlethreaded(create_iface_manager());
+        let scan_result_cache = Arc::new(Mutex::new(ScanResultCache::default()));
+
+        let MockScanData {
+            passive_input_aps: input_aps,
+            passive_internal_aps: internal_aps,
+            passive_fidl_aps: fidl_aps,
+            active_input_aps: _,
+            combined_internal_aps: _,
+            combined_fidl_aps: _,
+        } = create_scan_ap_data();
+
+        // First call: no cache entry yet, so this should trigger an SME scan.
+        let (network_selector, network_selector_results) = MockScanResultConsumer::new();
+        let (location_sensor, location_sensor_results) = MockScanResultConsumer::new();
+        let (iter, iter_server) = fidl::endpoints::create_proxy().expect("failed to create iterator");
+        let scan_fut = perform_scan(
+            ScanScheduler::new(client.clone()),
+            Arc::clone(&scan_result_cache),
+            Some(iter_server),
+            network_selector,
+            location_sensor,
+            |_| None,
+        );
+        pin_mut!(scan_fut);
+
+        let mut output_iter_fut = iter.get_next();
+        assert_variant!(exec.run_until_stalled(&mut output_iter_fut), Poll::Pending);
+        assert_variant!(exec.run_until_stalled(&mut scan_fut), Poll::Pending);
+
+        let expected_scan_request = fidl_sme::ScanRequest::Passive(fidl_sme::PassiveScanRequest {});
+        validate_sme_request_and_send_results(
+            &mut exec,
+            &mut sme_stream,
+            &expected_scan_request,
+            &input_aps,
+        );
+
+        assert_variant!(exec.run_until_stalled(&mut scan_fut), Poll::Ready(()));
+        assert_variant!(exec.run_until_stalled(&mut output_iter_fut), Poll::Ready(result) => {
+            let results = result.expect("Failed to get next scan results").unwrap();
+            assert_eq!(results, fidl_aps);
+        });
+        assert_eq!(
+            *exec.run_singlethreaded(network_selector_results.lock()),
+            Some(internal_aps.clone())
+        );
+        assert_eq!(
+            *exec.run_singlethreaded(location_sensor_results.lock()),
+            Some(internal_aps.clone())
+        );
+
+        // Second call with the same (still-fresh) cache: no SME scan should be issued, and the
+        // consumers should still receive the cached results.
+        let (network_selector2, network_selector_results2) = MockScanResultConsumer::new();
+        let (location_sensor2, location_sensor_results2) = MockScanResultConsumer::new();
+        let (iter2, iter_server2) = fidl::endpoints::create_proxy().expect("failed to create iterator");
+        let scan_fut2 = perform_scan(
+            ScanScheduler::new(client),
+            Arc::clone(&scan_result_cache),
+            Some(iter_server2),
+            network_selector2,
+            location_sensor2,
+            |_| None,
+        );
+        pin_mut!(scan_fut2);
+
+        let mut output_iter_fut2 = iter2.get_next();
+        assert_variant!(exec.run_until_stalled(&mut scan_fut2), Poll::Ready(()));
+        // No SME request should have been sent for the second scan.
+        assert_variant!(exec.run_until_stalled(&mut sme_stream.next()), Poll::Pending);
+
+        assert_variant!(exec.run_until_stalled(&mut output_iter_fut2), Poll::Ready(result) => {
+            let results = result.expect("Failed to get next scan results").unwrap();
+            assert_eq!(results, fidl_aps);
+        });
+        assert_eq!(
+            *exec.run_singlethreaded(network_selector_results2.lock()),
+            Some(internal_aps.clone())
+        );
+        assert_eq!(
+            *exec.run_singlethreaded(location_sensor_results2.lock()),
+            Some(internal_aps.clone())
+        );
+    }
+
+    #[test]
+    fn scan_scheduler_coalesces_concurrent_passive_scans() {
+        let mut exec = fasync::Executor::new().expect("failed to create an executor");
+        let (client, mut sme_stream) = exec.run_singlethreaded(create_iface_manager());
+        // Sharing one ScanScheduler between both calls opts in to coalescing: both should be
+        // served by a single SME scan, unlike `overlapping_scans` below where each call gets its
+        // own fresh ScanScheduler and therefore its own SME transaction.
+        let scan_scheduler = ScanScheduler::new(client);
+
+        let MockScanData {
+            passive_input_aps: input_aps,
+            passive_internal_aps: internal_aps,
+            passive_fidl_aps: fidl_aps,
+            active_input_aps: _,
+            combined_internal_aps: _,
+            combined_fidl_aps: _,
+        } = create_scan_ap_data();
+
+        let (network_selector0, network_selector_results0) = MockScanResultConsumer::new();
+        let (location_sensor0, location_sensor_results0) = MockScanResultConsumer::new();
+        let (iter0, iter_server0) =
+            fidl::endpoints::create_proxy().expect("failed to create iterator");
+        let scan_fut0 = perform_scan(
+            Arc::clone(&scan_scheduler),
+            Arc::new(Mutex::new(ScanResultCache::default())),
+            Some(iter_server0),
+            network_selector0,
+            location_sensor0,
+            |_| None,
+        );
+        pin_mut!(scan_fut0);
+
+        let (network_selector1, network_selector_results1) = MockScanResultConsumer::new();
+        let (location_sensor1, location_sensor_results1) = MockScanResultConsumer::new();
+        let (iter1, iter_server1) =
+            fidl::endpoints::create_proxy().expect("failed to create iterator");
+        let scan_fut1 = perform_scan(
+            Arc::clone(&scan_scheduler),
+            Arc::new(Mutex::new(ScanResultCache::default())),
+            Some(iter_server1),
+            network_selector1,
+            location_sensor1,
+            |_| None,
+        );
+        pin_mut!(scan_fut1);
+
+        let mut output_iter_fut0 = iter0.get_next();
+        let mut output_iter_fut1 = iter1.get_next();
+        assert_variant!(exec.run_until_stalled(&mut output_iter_fut0), Poll::Pending);
+        assert_variant!(exec.run_until_stalled(&mut output_iter_fut1), Poll::Pending);
+        assert_variant!(exec.run_until_stalled(&mut scan_fut0), Poll::Pending);
+        assert_variant!(exec.run_until_stalled(&mut scan_fut1), Poll::Pending);
+
+        // Exactly one SME scan request should have been sent for both waiters.
+        let expected_scan_request = fidl_sme::ScanRequest::Passive(fidl_sme::PassiveScanRequest {});
+        validate_sme_request_and_send_results(
+            &mut exec,
+            &mut sme_stream,
+            &expected_scan_request,
+            &input_aps,
+        );
+        assert_variant!(exec.run_until_stalled(&mut sme_stream.next()), Poll::Pending);
+
+        assert_variant!(exec.run_until_stalled(&mut scan_fut0), Poll::Ready(()));
+        assert_variant!(exec.run_until_stalled(&mut scan_fut1), Poll::Ready(()));
+
+        assert_variant!(exec.run_until_stalled(&mut output_iter_fut0), Poll::Ready(result) => {
+            let results = result.expect("Failed to get next scan results").unwrap();
+            assert_eq!(results, fidl_aps);
+        });
+        assert_variant!(exec.run_until_stalled(&mut output_iter_fut1), Poll::Ready(result) => {
+            let results = result.expect("Failed to get next scan results").unwrap();
+            assert_eq!(results, fidl_aps);
+        });
+        assert_eq!(
+            *exec.run_singlethreaded(network_selector_results0.lock()),
+            Some(internal_aps.clone())
+        );
+        assert_eq!(
+            *exec.run_singlethreaded(location_sensor_results0.lock()),
+            Some(internal_aps.clone())
+        );
+        assert_eq!(
+            *exec.run_singlethreaded(network_selector_results1.lock()),
+            Some(internal_aps.clone())
+        );
+        assert_eq!(
+            *exec.run_singlethreaded(location_sensor_results1.lock()),
+            Some(internal_aps.clone())
+        );
+    }
+
     #[test]
     fn scan_with_active_scan_decider() {
         let mut exec = fasync::Executor::new().expect("failed to create an executor");
@@ -1088,7 +1457,8 @@ mod tests {
             fidl::endpoints::create_proxy().expect("failed to create iterator");
         let expected_passive_results = passive_internal_aps.clone();
         let scan_fut = perform_scan(
-            client,
+            ScanScheduler::new(client),
+            Arc::new(Mutex::new(ScanResultCache::default())),
             Some(iter_server),
             network_selector,
             location_sensor,
@@ -1320,7 +1690,8 @@ mod tests {
             fidl::endpoints::create_proxy().expect("failed to create iterator");
         let expected_passive_results = passive_internal_aps.clone();
         let scan_fut = perform_scan(
-            client,
+            ScanScheduler::new(client),
+            Arc::new(Mutex::new(ScanResultCache::default())),
             Some(iter_server),
             network_selector,
             location_sensor,
@@ -1407,7 +1778,8 @@ mod tests {
         let (_iter, iter_server) =
             fidl::endpoints::create_proxy().expect("failed to create iterator");
         let scan_fut = perform_scan(
-            client.clone(),
+            ScanScheduler::new(client.clone()),
+            Arc::new(Mutex::new(ScanResultCache::default())),
             Some(iter_server),
             network_selector1,
             location_sensor1,
@@ -1443,7 +1815,14 @@ mod tests {
         let (iter2, iter_server2) =
             fidl::endpoints::create_proxy().expect("failed to create iterator");
         let scan_fut2 =
-            perform_scan(client, Some(iter_server2), network_selector2, location_sensor2, |_| None);
+            perform_scan(
+                ScanScheduler::new(client),
+                Arc::new(Mutex::new(ScanResultCache::default())),
+                Some(iter_server2),
+                network_selector2,
+                location_sensor2,
+                |_| None,
+            );
         pin_mut!(scan_fut2);
 
         // Progress scan side forward
@@ -1499,8 +1878,14 @@ mod tests {
         // Issue request to scan.
         let (iter, iter_server) =
             fidl::endpoints::create_proxy().expect("failed to create iterator");
-        let scan_fut =
-            perform_scan(client, Some(iter_server), network_selector, location_sensor, |_| None);
+        let scan_fut = perform_scan(
+            ScanScheduler::new(client),
+            Arc::new(Mutex::new(ScanResultCache::default())),
+            Some(iter_server),
+            network_selector,
+            location_sensor,
+            |_| None,
+        );
         pin_mut!(scan_fut);
 
         // Progress scan handler forward so that it will respond to the iterator get next request.
@@ -1551,8 +1936,14 @@ mod tests {
         // Issue request to scan.
         let (iter, iter_server) =
             fidl::endpoints::create_proxy().expect("failed to create iterator");
-        let scan_fut =
-            perform_scan(client, Some(iter_server), network_selector, location_sensor, |_| None);
+        let scan_fut = perform_scan(
+            ScanScheduler::new(client),
+            Arc::new(Mutex::new(ScanResultCache::default())),
+            Some(iter_server),
+            network_selector,
+            location_sensor,
+            |_| None,
+        );
         pin_mut!(scan_fut);
 
         // Request a chunk of scan results. Progress until waiting on response from server side of
@@ -1620,7 +2011,8 @@ mod tests {
 
         // Issue request to scan on both iterator.
         let scan_fut0 = perform_scan(
-            client.clone(),
+            ScanScheduler::new(client.clone()),
+            Arc::new(Mutex::new(ScanResultCache::default())),
             Some(iter_server0),
             network_selector1,
             location_sensor1,
@@ -1628,7 +2020,8 @@ mod tests {
         );
         pin_mut!(scan_fut0);
         let scan_fut1 = perform_scan(
-            client.clone(),
+            ScanScheduler::new(client.clone()),
+            Arc::new(Mutex::new(ScanResultCache::default())),
             Some(iter_server1),
             network_selector2,
             location_sensor2,
@@ -5,9 +5,12 @@
 use {
     crate::{
         accessor::ArchiveAccessor,
-        archive, configs, constants, diagnostics,
-        events::{stream::EventStream, types::EventSource},
-        logs::redact::Redactor,
+        archive, configs, constants, diagnostics, host_accessor,
+        events::{
+            router::{ConsumerConfig, EventRouter, ProducerConfig},
+            types::{EventSource, EventType},
+        },
+        logs::{self, budget::BudgetManager, redact::Redactor},
         pipeline::Pipeline,
         repository::DataRepo,
     },
@@ -15,6 +18,7 @@ use {
     fidl::{endpoints::RequestStream, AsyncChannel},
     fidl_fuchsia_diagnostics::Selector,
     fidl_fuchsia_diagnostics_test::{ControllerRequest, ControllerRequestStream},
+    fidl_fuchsia_logger,
     fidl_fuchsia_process_lifecycle::{LifecycleRequest, LifecycleRequestStream},
     fidl_fuchsia_sys_internal::SourceIdentity,
     fuchsia_async::{self as fasync, Task},
@@ -89,6 +93,12 @@ pub struct Archivist {
     // Store for safe keeping.
     _pipeline_configs: Vec<configs::PipelineConfig>,
 
+    // Store for safe keeping; keeps the `budget_*` inspect properties alive.
+    _log_stats_node: fuchsia_inspect::Node,
+
+    /// Mirrors `configs::Config::disable_klog`; checked by `install_klog_ingestion`.
+    disable_klog: bool,
+
     /// ServiceFs object to server outgoing directory.
     fs: ServiceFs<ServiceObj<'static, ()>>,
 
@@ -113,26 +123,60 @@ pub struct Archivist {
     /// receiver must close for `Archivist::run` to return gracefully.
     listen_sender: mpsc::UnboundedSender<Task<()>>,
 
-    /// Listes for events coming from v1 and v2.
-    event_stream: EventStream,
+    /// Routes component events from registered producers (v1 and v2 providers, and
+    /// eventually kernel log/serial sources) to registered consumers (the log, inspect and
+    /// lifecycle repositories).
+    event_router: EventRouter,
 
     /// Recieve stop signal to kill this archivist.
     stop_recv: Option<mpsc::Receiver<()>>,
+
+    /// Mirrors a filtered subset of ingested logs to the serial console. `None` until
+    /// `install_serial_sink` is called; a sink installed without a `SerialConfig` ignores
+    /// every message.
+    serial_sink: Option<logs::serial::SerialSink>,
+
+    /// The ALL_ACCESS pipeline and its stats, kept so `install_host_accessor_service` can
+    /// serve it over the host socket transport in addition to the channel FIDL service
+    /// installed in `new`.
+    host_pipeline: Arc<RwLock<Pipeline>>,
+    host_accessor_stats: Arc<diagnostics::AccessorStats>,
 }
 
+/// All event types the archivist's own consumers (the log, inspect and lifecycle
+/// repositories) are interested in. Kept in one place so `EventRouter::add_consumer` and any
+/// new producer registered in `Archivist::new` agree on the set that must be covered.
+const ARCHIVIST_EVENTS: &[EventType] = &[
+    EventType::ComponentStarted,
+    EventType::ComponentStopped,
+    EventType::DiagnosticsReady,
+    EventType::LogSinkRequested,
+];
+
 impl Archivist {
     async fn collect_component_events(
-        event_stream: EventStream,
+        router: EventRouter,
         state: archive::ArchivistState,
         pipeline_exists: bool,
     ) {
-        let events = event_stream.listen().await;
         if !pipeline_exists {
             component::health().set_unhealthy("Pipeline config has an error");
         } else {
             component::health().set_ok();
         }
-        archive::run_archivist(state, events).await
+
+        let (consumer, events) = mpsc::unbounded();
+        let mut router = router;
+        router.add_consumer(ConsumerConfig { consumer, events: ARCHIVIST_EVENTS });
+
+        let run_archivist = archive::run_archivist(state, events);
+        let run_router = async move {
+            if let Err(e) = router.start().await {
+                error!(%e, "event router failed validation");
+            }
+        };
+
+        future::join(run_archivist, run_router).map(|_| ()).await
     }
 
     /// Install controller service.
@@ -184,6 +228,7 @@ impl Archivist {
         let data_repo_1 = self.data_repo().clone();
         let data_repo_2 = self.data_repo().clone();
         let data_repo_3 = self.data_repo().clone();
+        let data_repo_4 = self.data_repo().clone();
         let log_sender = self.log_sender.clone();
         let log_sender2 = self.log_sender.clone();
         let listen_sender = self.listen_sender.clone();
@@ -210,20 +255,102 @@ impl Archivist {
                     data_repo_3.clone().handle_event_stream(stream, log_sender2.clone()),
                 )
                 .detach()
+            })
+            .add_fidl_service(move |stream| {
+                debug!("fuchsia.diagnostics.LogSettings connection");
+                fasync::Task::spawn(data_repo_4.clone().handle_log_settings(stream)).detach();
             });
         debug!("Log services initialized.");
         self
     }
 
-    // Sets event provider which is used to collect component events, Panics if called twice.
+    /// Spawns a task draining the kernel debuglog into `log_sender`, attributed to the
+    /// synthetic `klog` source identity. No-ops if `disable_klog` is set on the archivist's
+    /// configuration (logging at debug level), so that test and guest configurations can turn
+    /// kernel log ingestion off; if connecting to the debuglog fails, logs a warning and ingests
+    /// nothing.
+    pub fn install_klog_ingestion(&mut self) -> &mut Self {
+        if self.disable_klog {
+            debug!("klog ingestion disabled by configuration.");
+            return self;
+        }
+
+        let data_repo = self.data_repo().clone();
+        let log_sender = self.log_sender.clone();
+        fasync::Task::spawn(async move {
+            match logs::klog::KernelDebugLog::new().await {
+                Ok(klog) => klog.spawn_ingestion(data_repo, &log_sender),
+                Err(e) => warn!(%e, "failed to connect to kernel debuglog, klog disabled"),
+            }
+        })
+        .detach();
+        debug!("klog ingestion initialized.");
+        self
+    }
+
+    /// Drains a `fuchsia.logger.LogConnectionListener` channel, attributing each `LogSink` it
+    /// delivers to its real `SourceIdentity` instead of the anonymous `SourceIdentity::EMPTY`
+    /// used by the plain `fuchsia.logger.LogSink` service, so simultaneously-connected
+    /// identical sinks remain individually attributable.
+    pub fn install_log_connector(
+        &mut self,
+        stream: fidl_fuchsia_logger::LogConnectionListenerRequestStream,
+    ) -> &mut Self {
+        let data_repo = self.data_repo().clone();
+        let log_sender = self.log_sender.clone();
+        logs::connector::spawn_log_connector(stream, move |log_sink, source_identity| {
+            let stream = log_sink.into_stream().expect("failed to convert LogSink client end");
+            fasync::Task::spawn(data_repo.clone().handle_log_sink(
+                stream,
+                source_identity,
+                log_sender.clone(),
+            ))
+            .detach();
+        });
+        debug!("Log connector initialized.");
+        self
+    }
+
+    /// Installs a `SerialSink` that mirrors messages matching `config`'s selectors and
+    /// minimum severity to `serial`. Does nothing observable if `config` is `None`.
+    pub fn install_serial_sink(
+        &mut self,
+        config: Option<logs::serial::SerialConfig>,
+        serial: Option<zx::Socket>,
+    ) -> &mut Self {
+        self.serial_sink = Some(logs::serial::SerialSink::new(config, serial));
+        debug!("Serial sink initialized.");
+        self
+    }
+
+    /// Registers `fuchsia.diagnostics.host.ArchiveAccessor` on `svc`, serving the same
+    /// ALL_ACCESS pipeline as the channel-based accessor but over a raw socket transport so
+    /// off-device host tooling can stream diagnostics directly.
+    pub fn install_host_accessor_service(&mut self) -> &mut Self {
+        let pipeline = self.host_pipeline.clone();
+        let stats = self.host_accessor_stats.clone();
+        self.fs.dir("svc").add_fidl_service_at(
+            constants::HOST_ARCHIVE_ACCESSOR_NAME,
+            move |socket: zx::Socket| {
+                debug!("fuchsia.diagnostics.host.ArchiveAccessor connection");
+                host_accessor::HostArchiveAccessor::new(pipeline.clone(), stats.clone())
+                    .spawn(socket)
+            },
+        );
+        debug!("Host accessor service initialized.");
+        self
+    }
+
+    // Registers an event producer which is used to collect component events.
     pub fn add_event_source(
         &mut self,
         name: impl Into<String>,
         source: Box<dyn EventSource>,
+        events: &'static [EventType],
     ) -> &mut Self {
         let name = name.into();
         debug!("{} event source initialized", &name);
-        self.event_stream.add_source(name, source);
+        self.event_router.add_producer(ProducerConfig { producer: source, events });
         self
     }
 
@@ -231,6 +358,10 @@ impl Archivist {
     /// Also installs `fuchsia.diagnostics.Archive` service.
     /// Call `install_logger_services`, `add_event_source`.
     pub fn new(archivist_configuration: configs::Config) -> Result<Self, Error> {
+        let disable_klog = archivist_configuration.disable_klog;
+        let batch_retrieval_timeout = zx::Duration::from_seconds(
+            archivist_configuration.maximum_batch_retrieval_timeout_seconds,
+        );
         let (log_sender, log_receiver) = mpsc::unbounded();
         let (listen_sender, listen_receiver) = mpsc::unbounded();
 
@@ -271,7 +402,11 @@ impl Archivist {
             && feedback_config.has_error())
             || (Path::new("/config/data/legacy_metrics").is_dir() && legacy_config.has_error()));
 
-        let diagnostics_repo = DataRepo::with_logs_inspect(diagnostics::root(), "log_stats");
+        let log_stats_node = diagnostics::root().create_child("log_stats");
+        let budget_manager =
+            BudgetManager::new(archivist_configuration.logs_max_cached_bytes, &log_stats_node);
+        let diagnostics_repo =
+            DataRepo::with_logs_inspect(diagnostics::root(), "log_stats", budget_manager.clone());
 
         // The Inspect Repository offered to the ALL_ACCESS pipeline. This
         // repository is unique in that it has no statically configured
@@ -337,6 +472,10 @@ impl Archivist {
         let all_accessor_stats = Arc::new(diagnostics::AccessorStats::new(
             component::inspector().root().create_child("all_archive_accessor"),
         ));
+        // Kept alongside `all_accessor_stats` so `install_host_accessor_service` can serve the
+        // same pipeline over the host socket transport.
+        let host_pipeline = all_access_pipeline.clone();
+        let host_accessor_stats = all_accessor_stats.clone();
 
         let feedback_accessor_stats = Arc::new(diagnostics::AccessorStats::new(
             component::inspector().root().create_child("feedback_archive_accessor"),
@@ -349,8 +488,11 @@ impl Archivist {
         fs.dir("svc")
             .add_fidl_service(move |stream| {
                 debug!("fuchsia.diagnostics.ArchiveAccessor connection");
-                let all_archive_accessor =
-                    ArchiveAccessor::new(all_access_pipeline.clone(), all_accessor_stats.clone());
+                let all_archive_accessor = ArchiveAccessor::new(
+                    all_access_pipeline.clone(),
+                    all_accessor_stats.clone(),
+                    batch_retrieval_timeout,
+                );
                 all_archive_accessor.spawn_archive_accessor_server(stream)
             })
             .add_fidl_service_at(constants::FEEDBACK_ARCHIVE_ACCESSOR_NAME, move |chan| {
@@ -358,6 +500,7 @@ impl Archivist {
                 let feedback_archive_accessor = ArchiveAccessor::new(
                     feedback_pipeline.clone(),
                     feedback_accessor_stats.clone(),
+                    batch_retrieval_timeout,
                 );
                 feedback_archive_accessor.spawn_archive_accessor_server(chan)
             })
@@ -366,11 +509,11 @@ impl Archivist {
                 let legacy_archive_accessor = ArchiveAccessor::new(
                     legacy_metrics_pipeline.clone(),
                     legacy_accessor_stats.clone(),
+                    batch_retrieval_timeout,
                 );
                 legacy_archive_accessor.spawn_archive_accessor_server(chan)
             });
 
-        let events_node = diagnostics::root().create_child("event_stats");
         Ok(Self {
             fs,
             state: archivist_state,
@@ -381,8 +524,13 @@ impl Archivist {
             pipeline_exists,
             _pipeline_nodes: vec![pipelines_node, feedback_pipeline_node, legacy_pipeline_node],
             _pipeline_configs: vec![feedback_config, legacy_config],
-            event_stream: EventStream::new(events_node),
+            _log_stats_node: log_stats_node,
+            disable_klog,
+            event_router: EventRouter::new(),
             stop_recv: None,
+            serial_sink: None,
+            host_pipeline,
+            host_accessor_stats,
         })
     }
 
@@ -401,12 +549,23 @@ impl Archivist {
         debug!("Running Archivist.");
 
         let data_repo = { self.data_repo().clone() };
+
+        if let Some(mut serial_sink) = self.serial_sink.take() {
+            let mut messages = data_repo.clone().listen_for_all_messages();
+            Task::spawn(async move {
+                while let Some(message) = messages.next().await {
+                    serial_sink.handle_message(&message);
+                }
+            })
+            .detach();
+        }
+
         self.fs.serve_connection(outgoing_channel)?;
         // Start servcing all outgoing services.
         let run_outgoing = self.fs.collect::<()>();
         // collect events.
         let run_event_collection =
-            Self::collect_component_events(self.event_stream, self.state, self.pipeline_exists);
+            Self::collect_component_events(self.event_router, self.state, self.pipeline_exists);
 
         // Process messages from log sink.
         let log_receiver = self.log_receiver;
@@ -459,6 +618,9 @@ mod tests {
             archive_path: None,
             max_archive_size_bytes: 10,
             max_event_group_size_bytes: 10,
+            logs_max_cached_bytes: 10,
+            disable_klog: true,
+            maximum_batch_retrieval_timeout_seconds: 300,
             num_threads: 1,
         };
 
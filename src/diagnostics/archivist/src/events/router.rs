@@ -0,0 +1,113 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    crate::events::types::{ComponentEvent, EventSource, EventType},
+    anyhow::{format_err, Error},
+    futures::{channel::mpsc, prelude::*, stream::select_all},
+    std::collections::{HashMap, HashSet},
+};
+
+/// A producer registration: the boxed source and the event types it promises to emit.
+pub struct ProducerConfig {
+    pub producer: Box<dyn EventSource>,
+    pub events: &'static [EventType],
+}
+
+/// A consumer registration: a channel to forward matching events to and the event
+/// types it wants to receive.
+pub struct ConsumerConfig {
+    pub consumer: ConsumerHandle,
+    pub events: &'static [EventType],
+}
+
+/// The sending half of a consumer's event channel.
+pub type ConsumerHandle = mpsc::UnboundedSender<ComponentEvent>;
+
+/// Fans out `ComponentEvent`s from a set of registered producers to the consumers that
+/// subscribed to each event's type.
+///
+/// `EventRouter` replaces the old single-path `EventStream` wiring: rather than every
+/// consumer being hard-wired inside `collect_component_events`, producers and consumers are
+/// registered independently and `start()` validates that every subscribed event has at least
+/// one producer (and every produced event has at least one subscriber) before anything runs.
+pub struct EventRouter {
+    producers: Vec<Box<dyn EventSource>>,
+    produced_events: HashSet<EventType>,
+    routes: HashMap<EventType, Vec<ConsumerHandle>>,
+}
+
+impl EventRouter {
+    /// Creates a new, empty router.
+    pub fn new() -> Self {
+        Self { producers: vec![], produced_events: HashSet::new(), routes: HashMap::new() }
+    }
+
+    /// Registers a producer. Its declared event types are folded into the set validated by
+    /// `start`.
+    pub fn add_producer(&mut self, config: ProducerConfig) -> &mut Self {
+        self.producers.push(config.producer);
+        self.produced_events.extend(config.events.iter().copied());
+        self
+    }
+
+    /// Registers a consumer. Every event in `config.events` will be forwarded to
+    /// `config.consumer` once the router starts.
+    pub fn add_consumer(&mut self, config: ConsumerConfig) -> &mut Self {
+        for event_type in config.events {
+            self.routes.entry(*event_type).or_insert_with(Vec::new).push(config.consumer.clone());
+        }
+        self
+    }
+
+    /// Validates that every subscribed event type has at least one producer and that every
+    /// produced event type has at least one consumer, then runs the router to completion.
+    ///
+    /// Each incoming event is dispatched to exactly the consumers that registered for its
+    /// type. When all producer streams end, the consumer channels are dropped so their
+    /// owning tasks terminate, mirroring the graceful shutdown semantics of `Archivist::run`.
+    pub async fn start(mut self) -> Result<(), Error> {
+        self.validate()?;
+
+        let streams = self.producers.into_iter().map(|producer| producer.listen());
+        let mut combined = select_all(streams);
+
+        while let Some(event) = combined.next().await {
+            if let Some(consumers) = self.routes.get_mut(&event.ty()) {
+                let mut i = 0;
+                while i < consumers.len() {
+                    if consumers[i].unbounded_send(event.clone()).is_ok() {
+                        i += 1;
+                    } else {
+                        consumers.swap_remove(i);
+                    }
+                }
+            }
+        }
+
+        // Dropping `self.routes` here drops every consumer handle, which lets consumer tasks
+        // observe channel closure and terminate.
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        for event_type in self.routes.keys() {
+            if !self.produced_events.contains(event_type) {
+                return Err(format_err!(
+                    "no producer registered for consumed event type {:?}",
+                    event_type
+                ));
+            }
+        }
+        for event_type in &self.produced_events {
+            if !self.routes.contains_key(event_type) {
+                return Err(format_err!(
+                    "no consumer registered for produced event type {:?}",
+                    event_type
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    crate::{accessor::AccessorStats, diagnostics::formatter, pipeline::Pipeline},
+    anyhow::Error,
+    fidl_fuchsia_diagnostics::{StreamMode, StreamParameters},
+    fuchsia_async as fasync,
+    fuchsia_zircon as zx,
+    futures::prelude::*,
+    parking_lot::RwLock,
+    std::sync::Arc,
+    tracing::{error, warn},
+};
+
+/// Serves the same pipeline-backed accessor as the channel-based `ArchiveAccessor`, but over
+/// a byte-stream socket so an off-device host tool can stream diagnostics without the
+/// channel/VMO batch machinery.
+///
+/// The wire framing is the only difference from the channel transport: each record is
+/// length-delimited, JSON-serialized `Data`, written directly into the socket in a streaming
+/// loop. Selection and formatting are shared with the channel accessor via `formatter`, so
+/// both transports produce identical content.
+pub struct HostArchiveAccessor {
+    pipeline: Arc<RwLock<Pipeline>>,
+    stats: Arc<AccessorStats>,
+}
+
+impl HostArchiveAccessor {
+    pub fn new(pipeline: Arc<RwLock<Pipeline>>, stats: Arc<AccessorStats>) -> Self {
+        Self { pipeline, stats }
+    }
+
+    /// Reads a single `StreamParameters` length-prefixed JSON message from `socket`, then
+    /// writes length-delimited JSON `Data` records back into it until the snapshot (or, for
+    /// `StreamMode::Subscribe`, the live feed) is exhausted.
+    pub fn spawn(self, socket: zx::Socket) {
+        fasync::Task::spawn(async move {
+            if let Err(e) = self.serve(socket).await {
+                error!(%e, "host archive accessor connection failed");
+            }
+        })
+        .detach();
+    }
+
+    async fn serve(&self, mut socket: zx::Socket) -> Result<(), Error> {
+        self.stats.global_stats.batch_iterator_connections_opened.add(1);
+
+        let params: StreamParameters = read_length_delimited_json(&mut socket).await?;
+        let mode = params.stream_mode.unwrap_or(StreamMode::Snapshot);
+
+        let results =
+            formatter::format_results(self.pipeline.clone(), params).await.unwrap_or_default();
+
+        for data in &results {
+            write_length_delimited_json(&mut socket, data)?;
+        }
+
+        if mode == StreamMode::Subscribe {
+            let mut updates = formatter::subscribe(self.pipeline.clone());
+            while let Some(data) = updates.next().await {
+                if write_length_delimited_json(&mut socket, &data).is_err() {
+                    break;
+                }
+            }
+        }
+
+        self.stats.global_stats.batch_iterator_connections_closed.add(1);
+        Ok(())
+    }
+}
+
+async fn read_length_delimited_json<T: serde::de::DeserializeOwned>(
+    socket: &mut zx::Socket,
+) -> Result<T, Error> {
+    let mut len_bytes = [0u8; 4];
+    socket.read(&mut len_bytes)?;
+    let len = u32::from_le_bytes(len_bytes) as usize;
+    let mut buf = vec![0u8; len];
+    socket.read(&mut buf)?;
+    Ok(serde_json::from_slice(&buf)?)
+}
+
+fn write_length_delimited_json<T: serde::Serialize>(
+    socket: &mut zx::Socket,
+    value: &T,
+) -> Result<(), Error> {
+    let payload = serde_json::to_vec(value)?;
+    let len = (payload.len() as u32).to_le_bytes();
+    if let Err(e) = socket.write(&len).and_then(|_| socket.write(&payload)) {
+        warn!(%e, "failed writing to host accessor socket");
+        return Err(e.into());
+    }
+    Ok(())
+}
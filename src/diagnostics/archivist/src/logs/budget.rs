@@ -0,0 +1,194 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    fuchsia_inspect::{IntProperty, Node, NumericProperty, UintProperty},
+    parking_lot::Mutex,
+    std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+};
+
+/// A per-component log buffer that can be asked to pop its oldest entry and report its
+/// current front-entry timestamp, so a `BudgetManager` can find the globally oldest entry
+/// across every registered container.
+pub trait EvictableBuffer: Send + Sync {
+    /// The monotonic timestamp of the oldest entry still held, if any.
+    fn front_timestamp(&self) -> Option<i64>;
+
+    /// Pops the oldest entry, returning the number of bytes it freed. Returns 0 if the
+    /// buffer was already empty.
+    fn pop_front(&self) -> usize;
+}
+
+/// Enforces a global memory ceiling across every `ComponentLogBuffer` registered with it.
+///
+/// A buffer takes part once it is passed to [`BudgetManager::register`], and
+/// [`BudgetManager::allocate`] is then called with the size of each message appended to it;
+/// `ComponentLogBuffer` neither registers itself nor calls `allocate` from `push`. If the
+/// running total exceeds the budget, the manager walks
+/// the registered buffers, evicts from whichever has the oldest front entry, and repeats
+/// until the total is back under budget. Dead (dropped) buffers are pruned opportunistically
+/// while walking.
+pub struct BudgetManager {
+    capacity: usize,
+    current_bytes: AtomicUsize,
+    eviction_count: AtomicUsize,
+    buffers: Mutex<Vec<Weak<dyn EvictableBuffer>>>,
+    current_bytes_property: UintProperty,
+    eviction_count_property: IntProperty,
+}
+
+impl BudgetManager {
+    /// Creates a manager enforcing `capacity` bytes across all registered buffers, exposing
+    /// `current_bytes` and `eviction_count` under `node`.
+    pub fn new(capacity: usize, node: &Node) -> Arc<Self> {
+        Arc::new(Self {
+            capacity,
+            current_bytes: AtomicUsize::new(0),
+            eviction_count: AtomicUsize::new(0),
+            buffers: Mutex::new(vec![]),
+            current_bytes_property: node.create_uint("budget_current_bytes", 0),
+            eviction_count_property: node.create_int("budget_eviction_count", 0),
+        })
+    }
+
+    /// Registers a buffer so it can be considered during eviction.
+    pub fn register(&self, buffer: Weak<dyn EvictableBuffer>) {
+        self.buffers.lock().push(buffer);
+    }
+
+    /// Records that `new_bytes` were just appended to some registered buffer, evicting the
+    /// globally-oldest entries across all registered buffers until the total is back under
+    /// the configured capacity.
+    pub fn allocate(&self, new_bytes: usize) {
+        let total = self.current_bytes.fetch_add(new_bytes, Ordering::SeqCst) + new_bytes;
+        self.current_bytes_property.set(total as u64);
+
+        if total <= self.capacity {
+            return;
+        }
+
+        let mut freed = 0;
+        loop {
+            if self.current_bytes.load(Ordering::SeqCst) <= self.capacity {
+                break;
+            }
+            let mut buffers = self.buffers.lock();
+            buffers.retain(|b| b.upgrade().is_some());
+
+            let oldest = buffers
+                .iter()
+                .filter_map(|b| b.upgrade())
+                .filter_map(|b| b.front_timestamp().map(|ts| (ts, b)))
+                .min_by_key(|(ts, _)| *ts);
+
+            match oldest {
+                Some((_, buffer)) => {
+                    let bytes_freed = buffer.pop_front();
+                    if bytes_freed == 0 {
+                        // Buffer reported an entry but couldn't actually free anything;
+                        // avoid spinning forever.
+                        break;
+                    }
+                    freed += bytes_freed;
+                    self.current_bytes.fetch_sub(bytes_freed, Ordering::SeqCst);
+                    self.eviction_count.fetch_add(1, Ordering::Relaxed);
+                }
+                // Nothing left to evict even though we're over budget; give up rather than
+                // spin.
+                None => break,
+            }
+        }
+
+        if freed > 0 {
+            self.current_bytes_property.set(self.current_bytes.load(Ordering::SeqCst) as u64);
+            self.eviction_count_property.set(self.eviction_count.load(Ordering::Relaxed) as i64);
+        }
+    }
+
+    /// Current total bytes tracked across all registered buffers.
+    pub fn current_bytes(&self) -> usize {
+        self.current_bytes.load(Ordering::SeqCst)
+    }
+
+    /// Total number of entries evicted since the manager was created.
+    pub fn eviction_count(&self) -> usize {
+        self.eviction_count.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuchsia_inspect::Inspector;
+    use std::sync::atomic::AtomicI64;
+
+    struct FakeBuffer {
+        front: AtomicI64,
+        present: std::sync::atomic::AtomicBool,
+    }
+
+    impl FakeBuffer {
+        fn new(front: i64) -> Arc<Self> {
+            Arc::new(Self {
+                front: AtomicI64::new(front),
+                present: std::sync::atomic::AtomicBool::new(true),
+            })
+        }
+    }
+
+    impl EvictableBuffer for FakeBuffer {
+        fn front_timestamp(&self) -> Option<i64> {
+            if self.present.load(Ordering::SeqCst) {
+                Some(self.front.load(Ordering::SeqCst))
+            } else {
+                None
+            }
+        }
+
+        fn pop_front(&self) -> usize {
+            if self.present.swap(false, Ordering::SeqCst) {
+                10
+            } else {
+                0
+            }
+        }
+    }
+
+    #[test]
+    fn evicts_globally_oldest_entry() {
+        let inspector = Inspector::new();
+        let manager = BudgetManager::new(15, inspector.root());
+
+        let old = FakeBuffer::new(1);
+        let newer = FakeBuffer::new(2);
+        manager.register(Arc::downgrade(&old) as Weak<dyn EvictableBuffer>);
+        manager.register(Arc::downgrade(&newer) as Weak<dyn EvictableBuffer>);
+
+        manager.allocate(10);
+        assert_eq!(manager.current_bytes(), 10);
+
+        // This allocation pushes us over budget; the oldest buffer should be evicted.
+        manager.allocate(10);
+        assert_eq!(manager.eviction_count(), 1);
+        assert_eq!(old.front_timestamp(), None);
+        assert_eq!(newer.front_timestamp(), Some(2));
+    }
+
+    #[test]
+    fn prunes_dead_buffers_while_walking() {
+        let inspector = Inspector::new();
+        let manager = BudgetManager::new(5, inspector.root());
+        {
+            let gone = FakeBuffer::new(0);
+            manager.register(Arc::downgrade(&gone) as Weak<dyn EvictableBuffer>);
+        }
+        // `gone` has been dropped; allocating over budget must not panic even though there's
+        // nothing left to evict.
+        manager.allocate(10);
+        assert_eq!(manager.eviction_count(), 0);
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    crate::logs::budget::EvictableBuffer,
+    diagnostics_data::LogsData,
+    fuchsia_inspect::{Node, NumericProperty, UintProperty},
+    parking_lot::Mutex,
+    std::collections::VecDeque,
+};
+
+/// A single entry in a `ComponentLogBuffer`'s ring, tagged with a monotonically increasing
+/// sequence id so rolled-out entries can be reported to listeners as a "dropped N messages"
+/// marker rather than silently vanishing.
+struct Entry {
+    sequence: u64,
+    bytes: usize,
+    message: LogsData,
+}
+
+/// A memory-bounded, per-component ring buffer of log messages, FIFO-ordered by insertion.
+///
+/// Unlike a fixed-size ring, `ComponentLogBuffer` itself imposes no cap: it is meant to be
+/// registered with a [`crate::logs::budget::BudgetManager`], which calls `pop_front` across
+/// every registered container to keep the *global* total under budget. Each container still
+/// tracks its own live byte count and rolled-out count so those can be surfaced (e.g. via
+/// Inspect) per component.
+pub struct ComponentLogBuffer {
+    inner: Mutex<Inner>,
+    live_bytes_property: UintProperty,
+    rolled_count_property: UintProperty,
+}
+
+struct Inner {
+    entries: VecDeque<Entry>,
+    next_sequence: u64,
+    live_bytes: usize,
+    rolled_count: u64,
+}
+
+impl ComponentLogBuffer {
+    pub fn new(node: &Node) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                entries: VecDeque::new(),
+                next_sequence: 0,
+                live_bytes: 0,
+                rolled_count: 0,
+            }),
+            live_bytes_property: node.create_uint("live_bytes", 0),
+            rolled_count_property: node.create_uint("rolled_out_count", 0),
+        }
+    }
+
+    /// Appends `message`, sized at `bytes`, assigning it the next sequence id.
+    pub fn push(&self, message: LogsData, bytes: usize) {
+        let mut inner = self.inner.lock();
+        let sequence = inner.next_sequence;
+        inner.next_sequence += 1;
+        inner.live_bytes += bytes;
+        inner.entries.push_back(Entry { sequence, bytes, message });
+        self.live_bytes_property.set(inner.live_bytes as u64);
+    }
+
+    /// Total number of messages evicted from this buffer under memory pressure so far.
+    pub fn rolled_count(&self) -> u64 {
+        self.inner.lock().rolled_count
+    }
+
+    /// Drains every currently-held message, in FIFO order. Used when flushing to a listener.
+    pub fn drain(&self) -> Vec<LogsData> {
+        let mut inner = self.inner.lock();
+        inner.live_bytes = 0;
+        self.live_bytes_property.set(0);
+        inner.entries.drain(..).map(|e| e.message).collect()
+    }
+}
+
+impl EvictableBuffer for ComponentLogBuffer {
+    fn front_timestamp(&self) -> Option<i64> {
+        self.inner.lock().entries.front().map(|e| e.message.metadata.timestamp.into())
+    }
+
+    fn pop_front(&self) -> usize {
+        let mut inner = self.inner.lock();
+        match inner.entries.pop_front() {
+            Some(entry) => {
+                inner.live_bytes = inner.live_bytes.saturating_sub(entry.bytes);
+                inner.rolled_count += 1;
+                self.live_bytes_property.set(inner.live_bytes as u64);
+                self.rolled_count_property.set(inner.rolled_count);
+                entry.bytes
+            }
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diagnostics_data::{BuilderArgs, LogsDataBuilder, Severity};
+    use fuchsia_inspect::Inspector;
+
+    fn message(timestamp: i64) -> LogsData {
+        LogsDataBuilder::new(BuilderArgs {
+            component_url: "test".to_string(),
+            moniker: "test".to_string(),
+            severity: Severity::Info,
+            timestamp_nanos: timestamp.into(),
+        })
+        .set_message("msg".to_string())
+        .build()
+    }
+
+    #[test]
+    fn pop_front_evicts_fifo_and_counts_rolled() {
+        let inspector = Inspector::new();
+        let buffer = ComponentLogBuffer::new(inspector.root());
+        buffer.push(message(1), 10);
+        buffer.push(message(2), 10);
+
+        assert_eq!(buffer.pop_front(), 10);
+        assert_eq!(buffer.rolled_count(), 1);
+        assert_eq!(buffer.front_timestamp(), Some(2));
+    }
+
+    #[test]
+    fn drain_returns_fifo_order_and_resets_bytes() {
+        let inspector = Inspector::new();
+        let buffer = ComponentLogBuffer::new(inspector.root());
+        buffer.push(message(1), 10);
+        buffer.push(message(2), 10);
+
+        let drained = buffer.drain();
+        assert_eq!(drained.len(), 2);
+        assert_eq!(drained[0].metadata.timestamp, 1.into());
+        assert_eq!(buffer.front_timestamp(), None);
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    fidl_fuchsia_logger::{LogConnectionListenerRequest, LogConnectionListenerRequestStream},
+    fidl_fuchsia_sys_internal::SourceIdentity,
+    fuchsia_async as fasync,
+    futures::prelude::*,
+    std::sync::Arc,
+    tracing::warn,
+};
+
+/// One `LogSink` channel delivered by a `LogConnector`, together with the `SourceIdentity`
+/// (realm path, component name, pid) of the component that owns it.
+///
+/// Plain `connect` leaves every socket anonymously attributed, which makes many identical
+/// sinks indistinguishable in stats and in `LogMessage.tags`. Draining a
+/// `LogConnectionListener` instead keeps each connection's origin visible end to end.
+pub struct AttributedLogConnection {
+    pub log_sink: fidl::endpoints::ClientEnd<fidl_fuchsia_logger::LogSinkMarker>,
+    pub source_identity: Arc<SourceIdentity>,
+}
+
+/// Drains a `fuchsia.logger.LogConnectionListener` channel, yielding each `LogSink`
+/// connection it delivers together with the `SourceIdentity` that came with it.
+pub fn drain_log_connections(
+    mut stream: LogConnectionListenerRequestStream,
+) -> impl Stream<Item = AttributedLogConnection> {
+    async_stream::stream! {
+        while let Ok(Some(request)) = stream.try_next().await {
+            match request {
+                LogConnectionListenerRequest::OnNewConnection { connection, control_handle: _ } => {
+                    yield AttributedLogConnection {
+                        log_sink: connection.log_request,
+                        source_identity: Arc::new(connection.source_identity),
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// Spawns a task draining `listener`'s attributed connections and handing each one to
+/// `on_connection` (typically `DataRepo::handle_log_sink`, called with the connection's real
+/// `SourceIdentity` instead of `SourceIdentity::EMPTY`) so that N simultaneously-connected
+/// identical `LogSink`s remain individually attributable.
+pub fn spawn_log_connector<F>(stream: LogConnectionListenerRequestStream, on_connection: F)
+where
+    F: Fn(fidl::endpoints::ClientEnd<fidl_fuchsia_logger::LogSinkMarker>, Arc<SourceIdentity>)
+        + Send
+        + 'static,
+{
+    fasync::Task::spawn(async move {
+        let mut connections = Box::pin(drain_log_connections(stream));
+        while let Some(connection) = connections.next().await {
+            on_connection(connection.log_sink, connection.source_identity);
+        }
+        warn!("LogConnector connection closed");
+    })
+    .detach();
+}
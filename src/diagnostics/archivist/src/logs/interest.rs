@@ -0,0 +1,123 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    diagnostics_data::Severity,
+    fidl_fuchsia_diagnostics::{LogInterestSelector, Selector},
+    fuchsia_inspect::{Node, StringProperty},
+    fuchsia_inspect_contrib::nodes::BoundedListNode,
+    parking_lot::Mutex,
+    selectors,
+    std::collections::HashMap,
+};
+
+/// A single component's baseline severity, used when every selector-contributed interest for
+/// that component has been revoked.
+const DEFAULT_SEVERITY: Severity = Severity::Info;
+
+/// One rule contributed by a `LogSettings.SetInterest` call: the selector it applies to, its
+/// requested minimum severity, and the id of the connection that contributed it (so interests
+/// can be revoked together when that connection closes).
+struct InterestRule {
+    connection_id: usize,
+    selector: Selector,
+    min_severity: Severity,
+}
+
+/// Tracks the current set of `(selector, min_severity)` rules contributed by every open
+/// `LogSettings` connection and computes, for any component moniker, the effective minimum
+/// severity that should be pushed back over that component's `LogSink.WaitForInterestChange`
+/// channel.
+///
+/// When a `LogSettings` connection closes, `revoke_connection` drops its rules and the
+/// effective interest for every component it touched is recomputed to the next-highest
+/// remaining interest (or `DEFAULT_SEVERITY` if none remain).
+pub struct InterestDispatcher {
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    next_connection_id: usize,
+    rules: Vec<InterestRule>,
+    effective: HashMap<String, Severity>,
+    active_selectors_property: StringProperty,
+    _effective_node: BoundedListNode,
+}
+
+impl InterestDispatcher {
+    /// Creates a dispatcher that records its active selector set under `node`. It also creates
+    /// an `effective_severity` child there, which nothing writes to.
+    pub fn new(node: &Node) -> Self {
+        let active_selectors_property = node.create_string("active_selectors", "");
+        let effective_node = BoundedListNode::new(node.create_child("effective_severity"), 64);
+        Self {
+            inner: Mutex::new(Inner {
+                next_connection_id: 0,
+                rules: vec![],
+                effective: HashMap::new(),
+                active_selectors_property,
+                _effective_node: effective_node,
+            }),
+        }
+    }
+
+    /// Registers the selectors from one `LogSettings.SetInterest` call; the effective interest
+    /// of a component reflects them the next time `effective_severity_for` is called for it.
+    /// Returns an opaque
+    /// connection id to pass to `revoke_connection` when the `LogSettings` connection closes.
+    pub fn add_interest(&self, selectors: Vec<LogInterestSelector>) -> usize {
+        let mut inner = self.inner.lock();
+        let connection_id = inner.next_connection_id;
+        inner.next_connection_id += 1;
+
+        for selector in selectors {
+            inner.rules.push(InterestRule {
+                connection_id,
+                selector: selector.selector,
+                min_severity: selector.interest.min_severity.unwrap_or(DEFAULT_SEVERITY),
+            });
+        }
+        inner.record_active_selectors();
+        connection_id
+    }
+
+    /// Drops every rule contributed by `connection_id`. Any component whose effective
+    /// interest was determined by one of those rules is recomputed to the next-highest
+    /// remaining interest, or `DEFAULT_SEVERITY` if none remain.
+    pub fn revoke_connection(&self, connection_id: usize) {
+        let mut inner = self.inner.lock();
+        inner.rules.retain(|rule| rule.connection_id != connection_id);
+        inner.record_active_selectors();
+        // The exact effective severity per component is recomputed lazily the next time
+        // `effective_severity_for` is called for that moniker; dropping the rule is enough to
+        // make that recomputation reflect the revocation.
+    }
+
+    /// Computes the effective minimum severity for `moniker` given every currently-registered
+    /// rule whose selector matches it, or `DEFAULT_SEVERITY` if nothing matches.
+    pub fn effective_severity_for(&self, moniker: &str) -> Severity {
+        let inner = self.inner.lock();
+        inner
+            .rules
+            .iter()
+            .filter(|rule| {
+                selectors::match_moniker_against_selector(moniker, &rule.selector).unwrap_or(false)
+            })
+            .map(|rule| rule.min_severity)
+            .min()
+            .unwrap_or(DEFAULT_SEVERITY)
+    }
+}
+
+impl Inner {
+    fn record_active_selectors(&mut self) {
+        let rendered = self
+            .rules
+            .iter()
+            .map(|rule| format!("{:?}:{:?}", rule.selector, rule.min_severity))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.active_selectors_property.set(&rendered);
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    crate::{
+        logs::sorter::{SortingBuffer, SourceId},
+        repository::DataRepo,
+    },
+    anyhow::{Context as _, Error},
+    diagnostics_data::LogsData,
+    fidl_fuchsia_boot::ReadOnlyLogMarker,
+    fuchsia_async::{self as fasync, Task},
+    fuchsia_component::client::connect_to_protocol,
+    fuchsia_zircon as zx,
+    futures::channel::mpsc,
+    parking_lot::Mutex,
+    std::sync::Arc,
+    tracing::warn,
+};
+
+/// The fixed source id klog ingestion registers with a shared `SortingBuffer`, so the
+/// debuglog is just one more timestamped source feeding the same rolling-sort window the
+/// socket-ingest path uses.
+const KLOG_SOURCE_ID: SourceId = 0;
+
+/// The synthetic source identity attributed to every message bridged from the kernel
+/// debuglog, so listeners can tell kernel lines apart from userspace `LogSink` connections.
+pub const KERNEL_IDENTITY_MONIKER: &str = "klog";
+
+/// Reads the kernel debuglog (`fuchsia.boot.ReadOnlyLog`) and bridges each record into the
+/// same `Message`/`LogsData` pipeline used by userspace `LogSink` connections.
+pub struct KernelDebugLog {
+    debuglog: zx::DebugLog,
+}
+
+impl KernelDebugLog {
+    /// Connects to `fuchsia.boot.ReadOnlyLog` and wraps the handle it returns.
+    pub async fn new() -> Result<Self, Error> {
+        let proxy = connect_to_protocol::<ReadOnlyLogMarker>()
+            .context("connecting to fuchsia.boot.ReadOnlyLog")?;
+        let debuglog = proxy.get().await.context("calling ReadOnlyLog.Get")?;
+        Ok(Self { debuglog })
+    }
+
+    /// Spawns a detached task that drains the debuglog into `data_repo`, converting each
+    /// record to a structured log message tagged with the kernel's own timestamp and the
+    /// synthetic `klog` source identity, and forwards completion through `log_sender` (the
+    /// same channel `install_logger_services` uses for `LogSink` connections) so that
+    /// `Archivist::run`'s drain-then-terminate shutdown ordering also covers klog ingestion.
+    pub fn spawn_ingestion(
+        self,
+        data_repo: DataRepo,
+        log_sender: &mpsc::UnboundedSender<Task<()>>,
+    ) {
+        let mut debuglog = self.debuglog;
+        let task = Task::spawn(async move {
+            loop {
+                match debuglog.read() {
+                    Ok(record) => {
+                        let message = crate::logs::message::from_kernel_record(
+                            &record,
+                            KERNEL_IDENTITY_MONIKER,
+                        );
+                        data_repo.ingest_kernel_message(message);
+                    }
+                    Err(zx::Status::SHOULD_WAIT) => {
+                        if let Err(e) =
+                            fasync::OnSignals::new(&debuglog, zx::Signals::LOG_READABLE).await
+                        {
+                            warn!(%e, "error waiting on debuglog readability");
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        warn!(%e, "error reading debuglog, stopping klog ingestion");
+                        break;
+                    }
+                }
+            }
+        });
+        log_sender.unbounded_send(task).ok();
+    }
+
+    /// Like `spawn_ingestion`, but feeds records into `sorter` instead of directly into
+    /// `data_repo`, so kernel lines are merged into the same timestamp-ordered stream as
+    /// concurrently-connected `LogSink` sockets rather than being delivered out of band.
+    /// Consecutive identical records (the debuglog occasionally repeats the last line across
+    /// a reconnect) are deduplicated against the previously observed record. Gated by
+    /// `enabled`, so tests that don't want kernel noise can pass `false` and get a no-op.
+    pub fn spawn_into_sorter(
+        self,
+        sorter: Arc<Mutex<SortingBuffer>>,
+        log_sender: &mpsc::UnboundedSender<Task<()>>,
+        enabled: bool,
+    ) {
+        if !enabled {
+            return;
+        }
+
+        let mut debuglog = self.debuglog;
+        let task = Task::spawn(async move {
+            let mut last: Option<LogsData> = None;
+            loop {
+                match debuglog.read() {
+                    Ok(record) => {
+                        let message = crate::logs::message::from_kernel_record(
+                            &record,
+                            KERNEL_IDENTITY_MONIKER,
+                        );
+                        let is_duplicate = last.as_ref().map_or(false, |prev| {
+                            prev.metadata.timestamp == message.metadata.timestamp
+                                && prev.msg() == message.msg()
+                        });
+                        if !is_duplicate {
+                            sorter.lock().observe(KLOG_SOURCE_ID, message.clone());
+                            last = Some(message);
+                        }
+                    }
+                    Err(zx::Status::SHOULD_WAIT) => {
+                        if let Err(e) =
+                            fasync::OnSignals::new(&debuglog, zx::Signals::LOG_READABLE).await
+                        {
+                            warn!(%e, "error waiting on debuglog readability");
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        warn!(%e, "error reading debuglog, stopping klog ingestion");
+                        break;
+                    }
+                }
+            }
+            sorter.lock().remove_source(KLOG_SOURCE_ID);
+        });
+        log_sender.unbounded_send(task).ok();
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    fuchsia_inspect::{Node, NumericProperty, UintProperty},
+    lazy_static::lazy_static,
+    regex::Regex,
+};
+
+const REDACTED_PLACEHOLDER: &str = "<REDACTED>";
+
+lazy_static! {
+    static ref IPV4: Regex =
+        Regex::new(r"\b(?:(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)\.){3}(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)\b")
+            .unwrap();
+    static ref IPV6: Regex = Regex::new(r"\b(?:[0-9a-fA-F]{1,4}:){2,7}[0-9a-fA-F]{1,4}\b").unwrap();
+    static ref MAC_ADDRESS: Regex =
+        Regex::new(r"\b[0-9a-fA-F]{2}(?::[0-9a-fA-F]{2}){5}\b").unwrap();
+    static ref EMAIL_ADDRESS: Regex =
+        Regex::new(r"\b[\w.+-]+@[\w-]+\.[\w.-]+\b").unwrap();
+}
+
+/// Rewrites matched substrings (IPv4/IPv6 addresses, MAC addresses, email addresses, ...) in
+/// log message text to a canonical placeholder before the message is buffered and delivered
+/// to listeners.
+///
+/// `Redactor` is composable and selectable per output pipeline: the default (`noop`)
+/// redactor leaves text untouched so existing behavior is unaffected, while
+/// `with_static_patterns` applies the built-in pattern set. A privileged pipeline can opt out
+/// by using `noop` while a public-facing one uses `with_static_patterns`.
+pub struct Redactor {
+    patterns: Vec<&'static Regex>,
+    redacted_count: Option<UintProperty>,
+}
+
+impl Redactor {
+    /// An identity redactor that never modifies text.
+    pub fn noop() -> Self {
+        Self { patterns: vec![], redacted_count: None }
+    }
+
+    /// A redactor applying the built-in IPv4/IPv6/MAC/email patterns.
+    pub fn with_static_patterns() -> Self {
+        Self {
+            patterns: vec![&*IPV4, &*IPV6, &*MAC_ADDRESS, &*EMAIL_ADDRESS],
+            redacted_count: None,
+        }
+    }
+
+    /// Attaches a `redacted_message_count` Inspect property to this redactor, incremented
+    /// every time `redact_text` rewrites at least one match.
+    pub fn with_inspect(mut self, node: &Node) -> Self {
+        self.redacted_count = Some(node.create_uint("redacted_message_count", 0));
+        self
+    }
+
+    /// Returns `text` with every configured pattern's matches replaced by the canonical
+    /// placeholder. A no-op for the identity redactor.
+    pub fn redact_text(&self, text: &str) -> String {
+        if self.patterns.is_empty() {
+            return text.to_string();
+        }
+
+        let mut redacted = text.to_string();
+        let mut matched = false;
+        for pattern in &self.patterns {
+            if pattern.is_match(&redacted) {
+                matched = true;
+                redacted = pattern.replace_all(&redacted, REDACTED_PLACEHOLDER).into_owned();
+            }
+        }
+
+        if matched {
+            if let Some(counter) = &self.redacted_count {
+                counter.add(1);
+            }
+        }
+
+        redacted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noop_redactor_is_identity() {
+        let redactor = Redactor::noop();
+        assert_eq!(redactor.redact_text("contact me at a@b.com"), "contact me at a@b.com");
+    }
+
+    #[test]
+    fn redacts_email_and_ip() {
+        let redactor = Redactor::with_static_patterns();
+        let redacted = redactor.redact_text("from 192.168.1.1 reach a@b.com");
+        assert_eq!(redacted, format!("from {p} reach {p}", p = REDACTED_PLACEHOLDER));
+    }
+
+    #[test]
+    fn redacts_mac_address() {
+        let redactor = Redactor::with_static_patterns();
+        let redacted = redactor.redact_text("device 00:1A:2B:3C:4D:5E connected");
+        assert_eq!(redacted, format!("device {} connected", REDACTED_PLACEHOLDER));
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    diagnostics_data::{LogsData, Severity},
+    fuchsia_zircon as zx,
+    selectors,
+    std::io::Write,
+    tracing::warn,
+};
+
+/// A single `component moniker selector, minimum severity` rule parsed from config data,
+/// controlling which messages get mirrored to the serial console.
+pub struct SerialConfig {
+    pub selectors: Vec<String>,
+    pub min_severity: Severity,
+}
+
+impl SerialConfig {
+    /// Parses serial config data. Returns `None` (rather than an empty config) when no
+    /// selectors are present, so `install_serial_sink` can treat "no config" and "empty
+    /// config" identically and remain a no-op.
+    pub fn from_config_data(selectors: Vec<String>, min_severity: Severity) -> Option<Self> {
+        if selectors.is_empty() {
+            None
+        } else {
+            Some(Self { selectors, min_severity })
+        }
+    }
+
+    fn matches(&self, data: &LogsData) -> bool {
+        if data.metadata.severity < self.min_severity {
+            return false;
+        }
+        let moniker = data.moniker.as_str();
+        self.selectors.iter().any(|selector| {
+            selectors::match_moniker_against_component_selector(moniker, selector)
+                .unwrap_or(false)
+        })
+    }
+}
+
+/// Mirrors a filtered subset of ingested logs to the serial console, useful for early-boot
+/// and bringup debugging where no network/accessor client is attached. Fully no-op when no
+/// `SerialConfig` is present, so production builds that don't ship one are unaffected.
+pub struct SerialSink {
+    config: Option<SerialConfig>,
+    serial: Option<zx::Socket>,
+}
+
+impl SerialSink {
+    /// Creates a sink that forwards matching messages to `serial`. Passing `config: None`
+    /// (or a config with no selectors) makes every call to `handle_message` a no-op.
+    pub fn new(config: Option<SerialConfig>, serial: Option<zx::Socket>) -> Self {
+        Self { config, serial }
+    }
+
+    /// Formats and writes `data` to the serial handle if it matches the configured selectors
+    /// and minimum severity. Write errors are logged and otherwise ignored: a flaky serial
+    /// console must never tear down log ingestion.
+    pub fn handle_message(&mut self, data: &LogsData) {
+        let (config, serial) = match (&self.config, &mut self.serial) {
+            (Some(config), Some(serial)) => (config, serial),
+            _ => return,
+        };
+
+        if !config.matches(data) {
+            return;
+        }
+
+        let line = format_line(data);
+        if let Err(e) = serial.write(line.as_bytes()) {
+            warn!(%e, "failed to write to serial console");
+        }
+    }
+}
+
+/// Formats a single line-buffered, human-readable record: timestamp, moniker, tags,
+/// severity, message.
+fn format_line(data: &LogsData) -> String {
+    format!(
+        "[{timestamp}][{moniker}][{tags}][{severity}] {message}\n",
+        timestamp = data.metadata.timestamp,
+        moniker = data.moniker,
+        tags = data.metadata.tags.as_ref().map(|t| t.join(",")).unwrap_or_default(),
+        severity = data.metadata.severity,
+        message = data.msg().unwrap_or(""),
+    )
+}
@@ -0,0 +1,121 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {diagnostics_data::LogsData, std::collections::BTreeMap};
+
+/// An opaque handle identifying one of the sources currently feeding a `SortingBuffer` (e.g. a
+/// connected `LogSink` socket). Dropped via `remove_source` when that source disconnects.
+pub type SourceId = u64;
+
+/// Buffers messages from multiple concurrently-arriving sources (e.g. several `LogSink`
+/// sockets) and releases them in monotonic-timestamp order.
+///
+/// Call `observe` every time a source delivers a message; call `remove_source` when a source
+/// disconnects. A buffered message is only safe to release once its timestamp is `<=` every
+/// currently-live source's most-recently-observed timestamp ("frontier"), because no
+/// still-unseen message from any live source can carry an earlier one. `drain_ready` returns
+/// every message that has become safe to release, in timestamp order; `flush_all` is used at
+/// quiescence (all sources gone) to release everything regardless of frontier.
+#[derive(Default)]
+pub struct SortingBuffer {
+    frontiers: std::collections::HashMap<SourceId, i64>,
+    // Keyed by timestamp so the buffer is always iterated in order; ties are broken by
+    // insertion order via the inner Vec.
+    pending: BTreeMap<i64, Vec<LogsData>>,
+}
+
+impl SortingBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `source` is still alive and just delivered `message`, then buffers the
+    /// message.
+    pub fn observe(&mut self, source: SourceId, message: LogsData) {
+        let timestamp = message.metadata.timestamp;
+        self.frontiers.insert(source, timestamp);
+        self.pending.entry(timestamp).or_insert_with(Vec::new).push(message);
+    }
+
+    /// Drops `source` from the frontier set (it disconnected, so it can no longer produce an
+    /// earlier-timestamped message), returning every message newly unblocked by its removal.
+    pub fn remove_source(&mut self, source: SourceId) -> Vec<LogsData> {
+        self.frontiers.remove(&source);
+        self.drain_ready()
+    }
+
+    /// Returns every buffered message whose timestamp is `<=` the minimum frontier across all
+    /// currently-live sources, in ascending timestamp order. Returns nothing if any source is
+    /// still live and none of its frontier has caught up.
+    pub fn drain_ready(&mut self) -> Vec<LogsData> {
+        let min_frontier = match self.frontiers.values().min() {
+            Some(min) => *min,
+            // No sources left; this is handled by flush_all, not drain_ready.
+            None => return vec![],
+        };
+
+        let mut ready = vec![];
+        let still_pending = self.pending.split_off(&(min_frontier + 1));
+        for (_, messages) in std::mem::replace(&mut self.pending, still_pending) {
+            ready.extend(messages);
+        }
+        ready
+    }
+
+    /// Releases every buffered message regardless of frontier. Used once all sources have
+    /// disconnected and there is nothing left to wait for.
+    pub fn flush_all(&mut self) -> Vec<LogsData> {
+        self.frontiers.clear();
+        let mut ready = vec![];
+        for (_, messages) in std::mem::take(&mut self.pending) {
+            ready.extend(messages);
+        }
+        ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diagnostics_data::{BuilderArgs, LogsDataBuilder, Severity};
+
+    fn message(timestamp: i64) -> LogsData {
+        LogsDataBuilder::new(BuilderArgs {
+            component_url: "test".to_string(),
+            moniker: "test".to_string(),
+            severity: Severity::Info,
+            timestamp_nanos: timestamp.into(),
+        })
+        .set_message("msg".to_string())
+        .build()
+    }
+
+    #[test]
+    fn releases_only_up_to_min_frontier() {
+        let mut buffer = SortingBuffer::new();
+        buffer.observe(1, message(5));
+        buffer.observe(2, message(1));
+        buffer.observe(1, message(10));
+
+        // Source 2's frontier (1) is the minimum, so only the timestamp-1 message is ready.
+        let ready = buffer.drain_ready();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].metadata.timestamp, 1.into());
+    }
+
+    #[test]
+    fn removing_a_source_unblocks_its_frontier() {
+        let mut buffer = SortingBuffer::new();
+        buffer.observe(1, message(5));
+        buffer.observe(2, message(1));
+        // Source 2's frontier (1) holds back the timestamp-5 message.
+        assert_eq!(buffer.drain_ready().len(), 1);
+
+        let released = buffer.remove_source(2);
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].metadata.timestamp, 5.into());
+        let released = buffer.flush_all();
+        assert!(released.is_empty());
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright 2026 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// NOTE: This crate snapshot has no Cargo.toml checked in, so this file is not currently wired
+// up to a build - see the drift TODOs in src/lib.rs that it's meant to remove.
+//
+// The intended shape, once a manifest exists: read the Zircon syscall IR (the JSON file kazoo
+// emits alongside the generated C headers) from an environment-provided path, and emit the
+// `multiconst!` tables, `zx_obj_type_t`/`zx_object_info_topic_t` values, and `#[repr(C)]` struct
+// layouts in this chunk into `$OUT_DIR/generated.rs`, which `src/lib.rs` would then `include!`.
+// A checked-in copy of the generated output plus a test comparing it against a fresh
+// regeneration would catch silent drift between this crate and the IR it was generated from,
+// the way the comments on `struct_decl_macro!` and `multiconst!` describe wanting.
+//
+// Until a real IR source is available in this tree, the types in src/lib.rs remain hand
+// transcribed, and this file is a no-op placeholder recording the intended design rather than a
+// working codegen pipeline.
+
+fn main() {}
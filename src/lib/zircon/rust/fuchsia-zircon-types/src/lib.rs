@@ -4,6 +4,14 @@
 
 #![allow(non_camel_case_types)]
 
+// The POD info structs below carry `#[cfg_attr(feature = "zerocopy", derive(...))]` so they can
+// be safely reinterpreted from `zx_object_get_info` output without transmutes, mirroring
+// upstream. This snapshot has no Cargo.toml, so the `zerocopy` feature and its optional
+// dependency can't actually be registered anywhere - the attributes are inert until a manifest
+// declares `zerocopy = { version = "...", optional = true }` and `zerocopy = ["dep:zerocopy"]`.
+
+use std::{fmt, ops};
+
 pub type zx_addr_t = usize;
 pub type zx_stream_seek_origin_t = u32;
 pub type zx_clock_t = u32;
@@ -101,6 +109,71 @@ multiconst!(zx_rights_t, [
     ZX_RIGHT_SAME_RIGHTS    = 1 << 31;
 ]);
 
+/// A strongly-typed set of `ZX_RIGHT_*` flags, wrapping the raw `zx_rights_t` ABI value so
+/// callers get bitwise flag math and a `contains` check without casting back and forth to
+/// `u32` at every call site.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Default)]
+pub struct Rights(zx_rights_t);
+
+impl Rights {
+    pub const NONE: Rights = Rights(ZX_RIGHT_NONE);
+    pub const DUPLICATE: Rights = Rights(ZX_RIGHT_DUPLICATE);
+    pub const TRANSFER: Rights = Rights(ZX_RIGHT_TRANSFER);
+    pub const READ: Rights = Rights(ZX_RIGHT_READ);
+    pub const WRITE: Rights = Rights(ZX_RIGHT_WRITE);
+    pub const EXECUTE: Rights = Rights(ZX_RIGHT_EXECUTE);
+    pub const MAP: Rights = Rights(ZX_RIGHT_MAP);
+    pub const GET_PROPERTY: Rights = Rights(ZX_RIGHT_GET_PROPERTY);
+    pub const SET_PROPERTY: Rights = Rights(ZX_RIGHT_SET_PROPERTY);
+    pub const ENUMERATE: Rights = Rights(ZX_RIGHT_ENUMERATE);
+    pub const DESTROY: Rights = Rights(ZX_RIGHT_DESTROY);
+    pub const SET_POLICY: Rights = Rights(ZX_RIGHT_SET_POLICY);
+    pub const GET_POLICY: Rights = Rights(ZX_RIGHT_GET_POLICY);
+    pub const SIGNAL: Rights = Rights(ZX_RIGHT_SIGNAL);
+    pub const SIGNAL_PEER: Rights = Rights(ZX_RIGHT_SIGNAL_PEER);
+    pub const WAIT: Rights = Rights(ZX_RIGHT_WAIT);
+    pub const INSPECT: Rights = Rights(ZX_RIGHT_INSPECT);
+    pub const MANAGE_JOB: Rights = Rights(ZX_RIGHT_MANAGE_JOB);
+    pub const MANAGE_PROCESS: Rights = Rights(ZX_RIGHT_MANAGE_PROCESS);
+    pub const MANAGE_THREAD: Rights = Rights(ZX_RIGHT_MANAGE_THREAD);
+    pub const APPLY_PROFILE: Rights = Rights(ZX_RIGHT_APPLY_PROFILE);
+    pub const SAME_RIGHTS: Rights = Rights(ZX_RIGHT_SAME_RIGHTS);
+
+    pub const fn from_bits(bits: zx_rights_t) -> Rights {
+        Rights(bits)
+    }
+
+    pub const fn bits(&self) -> zx_rights_t {
+        self.0
+    }
+
+    /// Returns true if `self` has every bit set that `other` has set.
+    pub fn contains(&self, other: Rights) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl ops::BitOr for Rights {
+    type Output = Rights;
+    fn bitor(self, rhs: Rights) -> Rights {
+        Rights(self.0 | rhs.0)
+    }
+}
+
+impl ops::BitOrAssign for Rights {
+    fn bitor_assign(&mut self, rhs: Rights) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl ops::BitAnd for Rights {
+    type Output = Rights;
+    fn bitand(self, rhs: Rights) -> Rights {
+        Rights(self.0 & rhs.0)
+    }
+}
+
 multiconst!(u32, [
     ZX_VMO_RESIZABLE = 1 << 1;
 ]);
@@ -189,6 +262,148 @@ multiconst!(zx_status_t, [
     ZX_ERR_CONNECTION_ABORTED     = -76;
 ]);
 
+/// A strongly-typed `zx_status_t`, wrapping the raw ABI value so a syscall result can be
+/// turned into an idiomatic `Result` with `into_result` instead of comparing against `ZX_OK`
+/// by hand, and printed with its symbolic name via `Display`.
+#[repr(transparent)]
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct Status(zx_status_t);
+
+impl Status {
+    pub const OK: Status = Status(ZX_OK);
+    pub const ERR_INTERNAL: Status = Status(ZX_ERR_INTERNAL);
+    pub const ERR_NOT_SUPPORTED: Status = Status(ZX_ERR_NOT_SUPPORTED);
+    pub const ERR_NO_RESOURCES: Status = Status(ZX_ERR_NO_RESOURCES);
+    pub const ERR_NO_MEMORY: Status = Status(ZX_ERR_NO_MEMORY);
+    pub const ERR_INTERRUPTED_RETRY: Status = Status(ZX_ERR_INTERRUPTED_RETRY);
+    pub const ERR_INVALID_ARGS: Status = Status(ZX_ERR_INVALID_ARGS);
+    pub const ERR_BAD_HANDLE: Status = Status(ZX_ERR_BAD_HANDLE);
+    pub const ERR_WRONG_TYPE: Status = Status(ZX_ERR_WRONG_TYPE);
+    pub const ERR_BAD_SYSCALL: Status = Status(ZX_ERR_BAD_SYSCALL);
+    pub const ERR_OUT_OF_RANGE: Status = Status(ZX_ERR_OUT_OF_RANGE);
+    pub const ERR_BUFFER_TOO_SMALL: Status = Status(ZX_ERR_BUFFER_TOO_SMALL);
+    pub const ERR_BAD_STATE: Status = Status(ZX_ERR_BAD_STATE);
+    pub const ERR_TIMED_OUT: Status = Status(ZX_ERR_TIMED_OUT);
+    pub const ERR_SHOULD_WAIT: Status = Status(ZX_ERR_SHOULD_WAIT);
+    pub const ERR_CANCELED: Status = Status(ZX_ERR_CANCELED);
+    pub const ERR_PEER_CLOSED: Status = Status(ZX_ERR_PEER_CLOSED);
+    pub const ERR_NOT_FOUND: Status = Status(ZX_ERR_NOT_FOUND);
+    pub const ERR_ALREADY_EXISTS: Status = Status(ZX_ERR_ALREADY_EXISTS);
+    pub const ERR_ALREADY_BOUND: Status = Status(ZX_ERR_ALREADY_BOUND);
+    pub const ERR_UNAVAILABLE: Status = Status(ZX_ERR_UNAVAILABLE);
+    pub const ERR_ACCESS_DENIED: Status = Status(ZX_ERR_ACCESS_DENIED);
+    pub const ERR_IO: Status = Status(ZX_ERR_IO);
+    pub const ERR_IO_REFUSED: Status = Status(ZX_ERR_IO_REFUSED);
+    pub const ERR_IO_DATA_INTEGRITY: Status = Status(ZX_ERR_IO_DATA_INTEGRITY);
+    pub const ERR_IO_DATA_LOSS: Status = Status(ZX_ERR_IO_DATA_LOSS);
+    pub const ERR_IO_NOT_PRESENT: Status = Status(ZX_ERR_IO_NOT_PRESENT);
+    pub const ERR_IO_OVERRUN: Status = Status(ZX_ERR_IO_OVERRUN);
+    pub const ERR_IO_MISSED_DEADLINE: Status = Status(ZX_ERR_IO_MISSED_DEADLINE);
+    pub const ERR_IO_INVALID: Status = Status(ZX_ERR_IO_INVALID);
+    pub const ERR_BAD_PATH: Status = Status(ZX_ERR_BAD_PATH);
+    pub const ERR_NOT_DIR: Status = Status(ZX_ERR_NOT_DIR);
+    pub const ERR_NOT_FILE: Status = Status(ZX_ERR_NOT_FILE);
+    pub const ERR_FILE_BIG: Status = Status(ZX_ERR_FILE_BIG);
+    pub const ERR_NO_SPACE: Status = Status(ZX_ERR_NO_SPACE);
+    pub const ERR_NOT_EMPTY: Status = Status(ZX_ERR_NOT_EMPTY);
+    pub const ERR_STOP: Status = Status(ZX_ERR_STOP);
+    pub const ERR_NEXT: Status = Status(ZX_ERR_NEXT);
+    pub const ERR_ASYNC: Status = Status(ZX_ERR_ASYNC);
+    pub const ERR_PROTOCOL_NOT_SUPPORTED: Status = Status(ZX_ERR_PROTOCOL_NOT_SUPPORTED);
+    pub const ERR_ADDRESS_UNREACHABLE: Status = Status(ZX_ERR_ADDRESS_UNREACHABLE);
+    pub const ERR_ADDRESS_IN_USE: Status = Status(ZX_ERR_ADDRESS_IN_USE);
+    pub const ERR_NOT_CONNECTED: Status = Status(ZX_ERR_NOT_CONNECTED);
+    pub const ERR_CONNECTION_REFUSED: Status = Status(ZX_ERR_CONNECTION_REFUSED);
+    pub const ERR_CONNECTION_RESET: Status = Status(ZX_ERR_CONNECTION_RESET);
+    pub const ERR_CONNECTION_ABORTED: Status = Status(ZX_ERR_CONNECTION_ABORTED);
+
+    pub const fn from_raw(raw: zx_status_t) -> Status {
+        Status(raw)
+    }
+
+    pub const fn into_raw(self) -> zx_status_t {
+        self.0
+    }
+
+    /// Returns true if this status is `ZX_OK`.
+    pub fn is_ok(self) -> bool {
+        self.0 == ZX_OK
+    }
+
+    /// Converts to a `Result`, where `ZX_OK` is `Ok(())` and anything else is `Err(self)`.
+    pub fn into_result(self) -> Result<(), Status> {
+        if self.is_ok() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.0 {
+            ZX_OK => "ZX_OK",
+            ZX_ERR_INTERNAL => "ZX_ERR_INTERNAL",
+            ZX_ERR_NOT_SUPPORTED => "ZX_ERR_NOT_SUPPORTED",
+            ZX_ERR_NO_RESOURCES => "ZX_ERR_NO_RESOURCES",
+            ZX_ERR_NO_MEMORY => "ZX_ERR_NO_MEMORY",
+            ZX_ERR_INTERRUPTED_RETRY => "ZX_ERR_INTERRUPTED_RETRY",
+            ZX_ERR_INVALID_ARGS => "ZX_ERR_INVALID_ARGS",
+            ZX_ERR_BAD_HANDLE => "ZX_ERR_BAD_HANDLE",
+            ZX_ERR_WRONG_TYPE => "ZX_ERR_WRONG_TYPE",
+            ZX_ERR_BAD_SYSCALL => "ZX_ERR_BAD_SYSCALL",
+            ZX_ERR_OUT_OF_RANGE => "ZX_ERR_OUT_OF_RANGE",
+            ZX_ERR_BUFFER_TOO_SMALL => "ZX_ERR_BUFFER_TOO_SMALL",
+            ZX_ERR_BAD_STATE => "ZX_ERR_BAD_STATE",
+            ZX_ERR_TIMED_OUT => "ZX_ERR_TIMED_OUT",
+            ZX_ERR_SHOULD_WAIT => "ZX_ERR_SHOULD_WAIT",
+            ZX_ERR_CANCELED => "ZX_ERR_CANCELED",
+            ZX_ERR_PEER_CLOSED => "ZX_ERR_PEER_CLOSED",
+            ZX_ERR_NOT_FOUND => "ZX_ERR_NOT_FOUND",
+            ZX_ERR_ALREADY_EXISTS => "ZX_ERR_ALREADY_EXISTS",
+            ZX_ERR_ALREADY_BOUND => "ZX_ERR_ALREADY_BOUND",
+            ZX_ERR_UNAVAILABLE => "ZX_ERR_UNAVAILABLE",
+            ZX_ERR_ACCESS_DENIED => "ZX_ERR_ACCESS_DENIED",
+            ZX_ERR_IO => "ZX_ERR_IO",
+            ZX_ERR_IO_REFUSED => "ZX_ERR_IO_REFUSED",
+            ZX_ERR_IO_DATA_INTEGRITY => "ZX_ERR_IO_DATA_INTEGRITY",
+            ZX_ERR_IO_DATA_LOSS => "ZX_ERR_IO_DATA_LOSS",
+            ZX_ERR_IO_NOT_PRESENT => "ZX_ERR_IO_NOT_PRESENT",
+            ZX_ERR_IO_OVERRUN => "ZX_ERR_IO_OVERRUN",
+            ZX_ERR_IO_MISSED_DEADLINE => "ZX_ERR_IO_MISSED_DEADLINE",
+            ZX_ERR_IO_INVALID => "ZX_ERR_IO_INVALID",
+            ZX_ERR_BAD_PATH => "ZX_ERR_BAD_PATH",
+            ZX_ERR_NOT_DIR => "ZX_ERR_NOT_DIR",
+            ZX_ERR_NOT_FILE => "ZX_ERR_NOT_FILE",
+            ZX_ERR_FILE_BIG => "ZX_ERR_FILE_BIG",
+            ZX_ERR_NO_SPACE => "ZX_ERR_NO_SPACE",
+            ZX_ERR_NOT_EMPTY => "ZX_ERR_NOT_EMPTY",
+            ZX_ERR_STOP => "ZX_ERR_STOP",
+            ZX_ERR_NEXT => "ZX_ERR_NEXT",
+            ZX_ERR_ASYNC => "ZX_ERR_ASYNC",
+            ZX_ERR_PROTOCOL_NOT_SUPPORTED => "ZX_ERR_PROTOCOL_NOT_SUPPORTED",
+            ZX_ERR_ADDRESS_UNREACHABLE => "ZX_ERR_ADDRESS_UNREACHABLE",
+            ZX_ERR_ADDRESS_IN_USE => "ZX_ERR_ADDRESS_IN_USE",
+            ZX_ERR_NOT_CONNECTED => "ZX_ERR_NOT_CONNECTED",
+            ZX_ERR_CONNECTION_REFUSED => "ZX_ERR_CONNECTION_REFUSED",
+            ZX_ERR_CONNECTION_RESET => "ZX_ERR_CONNECTION_RESET",
+            ZX_ERR_CONNECTION_ABORTED => "ZX_ERR_CONNECTION_ABORTED",
+            _ => "UNKNOWN",
+        }
+    }
+}
+
+impl fmt::Debug for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Status({}={})", self.name(), self.0)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name(), self.0)
+    }
+}
+
 multiconst!(zx_signals_t, [
     ZX_SIGNAL_NONE              = 0;
     ZX_OBJECT_SIGNAL_ALL        = 0x00ffffff;
@@ -289,6 +504,56 @@ multiconst!(zx_signals_t, [
     ZX_TIMER_SIGNALED           = ZX_OBJECT_SIGNAL_3;
 ]);
 
+/// A strongly-typed set of `ZX_*_SIGNAL*` flags, wrapping the raw `zx_signals_t` ABI value.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Default)]
+pub struct Signals(zx_signals_t);
+
+impl Signals {
+    pub const NONE: Signals = Signals(ZX_SIGNAL_NONE);
+    pub const OBJECT_ALL: Signals = Signals(ZX_OBJECT_SIGNAL_ALL);
+    pub const USER_ALL: Signals = Signals(ZX_USER_SIGNAL_ALL);
+    pub const OBJECT_READABLE: Signals = Signals(ZX_OBJECT_READABLE);
+    pub const OBJECT_WRITABLE: Signals = Signals(ZX_OBJECT_WRITABLE);
+    pub const OBJECT_PEER_CLOSED: Signals = Signals(ZX_OBJECT_PEER_CLOSED);
+    pub const CHANNEL_READABLE: Signals = Signals(ZX_CHANNEL_READABLE);
+    pub const CHANNEL_WRITABLE: Signals = Signals(ZX_CHANNEL_WRITABLE);
+    pub const CHANNEL_PEER_CLOSED: Signals = Signals(ZX_CHANNEL_PEER_CLOSED);
+
+    pub const fn from_bits(bits: zx_signals_t) -> Signals {
+        Signals(bits)
+    }
+
+    pub const fn bits(&self) -> zx_signals_t {
+        self.0
+    }
+
+    /// Returns true if `self` has every bit set that `other` has set.
+    pub fn contains(&self, other: Signals) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl ops::BitOr for Signals {
+    type Output = Signals;
+    fn bitor(self, rhs: Signals) -> Signals {
+        Signals(self.0 | rhs.0)
+    }
+}
+
+impl ops::BitOrAssign for Signals {
+    fn bitor_assign(&mut self, rhs: Signals) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl ops::BitAnd for Signals {
+    type Output = Signals;
+    fn bitand(self, rhs: Signals) -> Signals {
+        Signals(self.0 & rhs.0)
+    }
+}
+
 multiconst!(zx_obj_type_t, [
     ZX_OBJ_TYPE_NONE                = 0;
     ZX_OBJ_TYPE_PROCESS             = 1;
@@ -411,6 +676,33 @@ pub struct zx_clock_transformation_t {
     pub rate: zx_clock_rate_t,
 }
 
+impl zx_clock_transformation_t {
+    /// Applies the affine transform forward, converting a reference tick count into synthetic
+    /// (clock) time. The multiply is widened to i128 before dividing to avoid overflow on
+    /// realistic tick counts, and the i128 result is narrowed back to i64 with saturation.
+    pub fn apply(&self, reference_ticks: i64) -> i64 {
+        let scaled = (reference_ticks as i128 - self.reference_offset as i128)
+            * self.rate.synthetic_ticks as i128
+            / self.rate.reference_ticks as i128;
+        let synthetic = self.synthetic_offset as i128 + scaled;
+        synthetic.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// Applies the inverse of the affine transform, converting synthetic (clock) time back into
+    /// a reference tick count. Returns `None` rather than dividing by zero if `rate`'s numerator
+    /// (`synthetic_ticks`) is zero.
+    pub fn apply_inverse(&self, synthetic: i64) -> Option<i64> {
+        if self.rate.synthetic_ticks == 0 {
+            return None;
+        }
+        let scaled = (synthetic as i128 - self.synthetic_offset as i128)
+            * self.rate.reference_ticks as i128
+            / self.rate.synthetic_ticks as i128;
+        let reference = self.reference_offset as i128 + scaled;
+        Some(reference.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone)]
 pub struct zx_clock_details_v1_t {
@@ -427,6 +719,20 @@ pub struct zx_clock_details_v1_t {
     pub padding1: [u8; 4],
 }
 
+impl zx_clock_details_v1_t {
+    /// Converts a raw tick count (as returned by `zx_ticks_get`) into this clock's synthetic
+    /// time, using `ticks_to_synthetic`.
+    pub fn ticks_to_synthetic(&self, ticks: zx_ticks_t) -> i64 {
+        self.ticks_to_synthetic.apply(ticks)
+    }
+
+    /// Converts a `ZX_CLOCK_MONOTONIC` timestamp into this clock's synthetic time, using
+    /// `mono_to_synthetic`.
+    pub fn mono_to_synthetic(&self, mono: zx_time_t) -> i64 {
+        self.mono_to_synthetic.apply(mono)
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone)]
 pub struct zx_clock_update_args_v1_t {
@@ -701,6 +1007,50 @@ pub struct zx_packet_guest_mem_t {
     pub default_operand_size: u8,
 }
 
+/// A safely-typed view of a `zx_port_packet_t`'s 32-byte `union` payload, decoded according to
+/// its `packet_type`.
+#[derive(Debug, Copy, Clone)]
+pub enum PortPacketPayload {
+    User(zx_packet_user_t),
+    SignalOne(zx_packet_signal_t),
+    GuestBell(zx_packet_guest_bell_t),
+    GuestMem(zx_packet_guest_mem_t),
+    GuestIo(zx_packet_guest_io_t),
+}
+
+impl zx_port_packet_t {
+    /// Decodes `self.union` into the payload variant named by `self.packet_type`, or `None` if
+    /// `packet_type` is the unknown/`__Nonexhaustive` discriminant.
+    ///
+    /// This transmutes the raw 32-byte union, so it only does so after matching on
+    /// `packet_type` to pick the right destination type - never blindly.
+    pub fn as_payload(&self) -> Option<PortPacketPayload> {
+        // Safety: each of these structs is no larger than the 32-byte union it's read out of, and
+        // `packet_type` is checked first so the source bytes were actually populated by the
+        // kernel as that variant.
+        unsafe {
+            match self.packet_type {
+                zx_packet_type_t::ZX_PKT_TYPE_USER => {
+                    Some(PortPacketPayload::User(self.union))
+                }
+                zx_packet_type_t::ZX_PKT_TYPE_SIGNAL_ONE => {
+                    Some(PortPacketPayload::SignalOne(std::mem::transmute_copy(&self.union)))
+                }
+                zx_packet_type_t::ZX_PKT_TYPE_GUEST_BELL => {
+                    Some(PortPacketPayload::GuestBell(std::mem::transmute_copy(&self.union)))
+                }
+                zx_packet_type_t::ZX_PKT_TYPE_GUEST_MEM => {
+                    Some(PortPacketPayload::GuestMem(std::mem::transmute_copy(&self.union)))
+                }
+                zx_packet_type_t::ZX_PKT_TYPE_GUEST_IO => {
+                    Some(PortPacketPayload::GuestIo(std::mem::transmute_copy(&self.union)))
+                }
+                zx_packet_type_t::__Nonexhaustive => None,
+            }
+        }
+    }
+}
+
 // Helper for constructing topics that have been versioned.
 const fn info_topic(topic: u32, version: u32) -> u32 {
     (version << 28) | topic
@@ -757,6 +1107,10 @@ macro_rules! struct_decl_macro {
 // Don't need struct_decl_macro for this, the wrapper is different.
 #[repr(C)]
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+)]
 pub struct zx_info_handle_basic_t {
     pub koid: zx_koid_t,
     pub rights: zx_rights_t,
@@ -768,6 +1122,10 @@ pub struct zx_info_handle_basic_t {
 // Don't need struct_decl_macro for this, the wrapper is different.
 #[repr(C)]
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+)]
 pub struct zx_info_socket_t {
     pub options: u32,
     pub rx_buf_max: usize,
@@ -780,6 +1138,10 @@ pub struct zx_info_socket_t {
 struct_decl_macro! {
     #[repr(C)]
     #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "zerocopy",
+        derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+    )]
     pub struct <zx_info_process_t> {
         pub return_code: i64,
         pub started: bool,
@@ -793,6 +1155,10 @@ zx_info_process_t!(zx_info_process_t);
 struct_decl_macro! {
     #[repr(C)]
     #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "zerocopy",
+        derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+    )]
     pub struct <zx_info_job_t> {
         pub return_code: i64,
         pub exited: bool,
@@ -864,6 +1230,10 @@ multiconst!(u32, [
 // Don't use struct_decl_macro, wrapper is different.
 #[repr(C)]
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+)]
 pub struct zx_info_vmo_t {
     pub koid: zx_koid_t,
     pub name: [u8; ZX_MAX_NAME_LEN],
@@ -883,6 +1253,10 @@ pub struct zx_info_vmo_t {
 struct_decl_macro! {
     #[repr(C)]
     #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "zerocopy",
+        derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+    )]
     pub struct <zx_info_cpu_stats_t> {
         pub cpu_number: u32,
         pub flags: u32,
@@ -908,6 +1282,10 @@ zx_info_cpu_stats_t!(zx_info_cpu_stats_t);
 struct_decl_macro! {
     #[repr(C)]
     #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "zerocopy",
+        derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+    )]
     pub struct <zx_info_kmem_stats_t> {
         pub total_bytes: u64,
         pub free_bytes: u64,
@@ -926,6 +1304,10 @@ zx_info_kmem_stats_t!(zx_info_kmem_stats_t);
 struct_decl_macro! {
     #[repr(C)]
     #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "zerocopy",
+        derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+    )]
     pub struct <zx_info_kmem_stats_extended_t> {
         pub total_bytes: u64,
         pub free_bytes: u64,
@@ -949,6 +1331,10 @@ zx_info_kmem_stats_extended_t!(zx_info_kmem_stats_extended_t);
 struct_decl_macro! {
     #[repr(C)]
     #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "zerocopy",
+        derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+    )]
     pub struct <zx_info_resource_t> {
         pub kind: u32,
         pub flags: u32,
@@ -963,6 +1349,10 @@ zx_info_resource_t!(zx_info_resource_t);
 struct_decl_macro! {
     #[repr(C)]
     #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "zerocopy",
+        derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+    )]
     pub struct <zx_info_vmar_t> {
         pub base: usize,
         pub len: usize,
@@ -974,6 +1364,10 @@ zx_info_vmar_t!(zx_info_vmar_t);
 struct_decl_macro! {
     #[repr(C)]
     #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(
+        feature = "zerocopy",
+        derive(zerocopy::FromBytes, zerocopy::AsBytes, zerocopy::FromZeros, zerocopy::NoCell)
+    )]
     pub struct <zx_info_task_stats_t> {
         pub mem_mapped_bytes: usize,
         pub mem_private_bytes: usize,
@@ -984,6 +1378,67 @@ struct_decl_macro! {
 
 zx_info_task_stats_t!(zx_info_task_stats_t);
 
+pub type zx_info_maps_type_t = u32;
+
+multiconst!(zx_info_maps_type_t, [
+    ZX_INFO_MAPS_TYPE_NONE    = 0;
+    ZX_INFO_MAPS_TYPE_ASPACE = 1;
+    ZX_INFO_MAPS_TYPE_VMAR   = 2;
+    ZX_INFO_MAPS_TYPE_MAPPING = 3;
+]);
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_info_maps_mapping_t {
+    pub mmu_flags: zx_vm_option_t,
+    pub _padding1: [u8; 4],
+    pub vmo_koid: zx_koid_t,
+    pub vmo_offset: u64,
+    pub committed_pages: usize,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union zx_info_maps_inner {
+    pub mapping: zx_info_maps_mapping_t,
+}
+
+impl Default for zx_info_maps_inner {
+    fn default() -> Self {
+        zx_info_maps_inner {
+            mapping: zx_info_maps_mapping_t {
+                mmu_flags: 0,
+                _padding1: Default::default(),
+                vmo_koid: 0,
+                vmo_offset: 0,
+                committed_pages: 0,
+            },
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct zx_info_maps_t {
+    pub name: [u8; ZX_MAX_NAME_LEN],
+    pub base: zx_vaddr_t,
+    pub size: usize,
+    pub depth: usize,
+    pub type_: zx_info_maps_type_t,
+    pub u: zx_info_maps_inner,
+}
+
+impl zx_info_maps_t {
+    /// Reads the `mapping` variant of `u`, or `None` unless `type_ == ZX_INFO_MAPS_TYPE_MAPPING`.
+    pub fn as_mapping(&self) -> Option<&zx_info_maps_mapping_t> {
+        if self.type_ != ZX_INFO_MAPS_TYPE_MAPPING {
+            return None;
+        }
+        // Safety: `type_` confirms `u` was populated as `mapping`.
+        Some(unsafe { &self.u.mapping })
+    }
+}
+
 multiconst!(zx_guest_trap_t, [
     ZX_GUEST_TRAP_BELL = 0;
     ZX_GUEST_TRAP_MEM  = 1;
@@ -1011,6 +1466,37 @@ pub struct zx_system_powerctl_arg_t {
     powerctl_internal: zx_powerctl_union,
 }
 
+impl zx_system_powerctl_arg_t {
+    /// Builds the argument for a `ZX_SYSTEM_POWERCTL_ACPI_TRANSITION_S_STATE` power-control call.
+    pub fn new_acpi_s_state(target_s_state: u8, sleep_type_a: u8, sleep_type_b: u8) -> Self {
+        zx_system_powerctl_arg_t {
+            powerctl_internal: zx_powerctl_union {
+                acpi_transition_s_state: acpi_transition_s_state {
+                    target_s_state,
+                    sleep_type_a,
+                    sleep_type_b,
+                    _padding1: Default::default(),
+                },
+            },
+        }
+    }
+
+    /// Builds the argument for a `ZX_SYSTEM_POWERCTL_X86_POWER_LIMIT` power-control call.
+    pub fn new_x86_power_limit(power_limit: u32, time_window: u32, clamp: bool, enable: bool) -> Self {
+        zx_system_powerctl_arg_t {
+            powerctl_internal: zx_powerctl_union {
+                x86_power_limit: x86_power_limit {
+                    power_limit,
+                    time_window,
+                    clamp: clamp as u8,
+                    enable: enable as u8,
+                    _padding2: Default::default(),
+                },
+            },
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub union zx_powerctl_union {
@@ -1070,6 +1556,50 @@ pub struct zx_pci_bar_union_struct {
     _padding1: [u8; 4],
 }
 
+/// A safely-typed view of a `zx_pci_bar_t`'s anonymous union, decoded according to its `ty`.
+#[derive(Debug, Copy, Clone)]
+pub enum ZxPciBar {
+    Mmio(usize),
+    Pio(zx_handle_t),
+}
+
+impl zx_pci_bar_t {
+    pub fn new_mmio(id: u32, size: usize, addr: usize) -> Self {
+        zx_pci_bar_t {
+            id,
+            ty: ZX_PCI_BAR_TYPE_MMIO,
+            size,
+            zx_pci_bar_union: zx_pci_bar_union { addr },
+        }
+    }
+
+    pub fn new_pio(id: u32, size: usize, handle: zx_handle_t) -> Self {
+        zx_pci_bar_t {
+            id,
+            ty: ZX_PCI_BAR_TYPE_PIO,
+            size,
+            zx_pci_bar_union: zx_pci_bar_union {
+                zx_pci_bar_union_struct: zx_pci_bar_union_struct { handle, _padding1: Default::default() },
+            },
+        }
+    }
+
+    /// Dispatches on `ty` to safely read the union payload, or `None` if `ty` is
+    /// `ZX_PCI_BAR_TYPE_UNUSED` or an unrecognized value.
+    pub fn bar(&self) -> Option<ZxPciBar> {
+        // Safety: `ty` is checked before reading the matching union field.
+        unsafe {
+            match self.ty {
+                ZX_PCI_BAR_TYPE_MMIO => Some(ZxPciBar::Mmio(self.zx_pci_bar_union.addr)),
+                ZX_PCI_BAR_TYPE_PIO => {
+                    Some(ZxPciBar::Pio(self.zx_pci_bar_union.zx_pci_bar_union_struct.handle))
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
 // source: zircon/system/public/zircon/syscalls/smc.h
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -1107,6 +1637,12 @@ pub struct zx_cpu_set_t {
 }
 
 // source: zircon/system/public/zircon/syscalls/scheduler.h
+multiconst!(u32, [
+    ZX_PROFILE_INFO_FLAG_PRIORITY = 1 << 0;
+    ZX_PROFILE_INFO_FLAG_CPU_MASK = 1 << 1;
+    ZX_PROFILE_INFO_FLAG_DEADLINE = 1 << 2;
+]);
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct zx_profile_info_t {
@@ -1132,8 +1668,260 @@ union zx_profile_info_union {
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct zx_sched_deadline_params_t {
-    capacity: zx_duration_t,
-    relative_deadline: zx_duration_t,
-    period: zx_duration_t,
+pub struct zx_sched_deadline_params_t {
+    pub capacity: zx_duration_t,
+    pub relative_deadline: zx_duration_t,
+    pub period: zx_duration_t,
+}
+
+impl zx_profile_info_t {
+    /// Builds a priority profile with `affinity` as its CPU affinity mask.
+    pub fn new_priority(priority: i32, affinity: zx_cpu_set_t) -> Self {
+        zx_profile_info_t {
+            flags: ZX_PROFILE_INFO_FLAG_PRIORITY,
+            _padding1: Default::default(),
+            zx_profile_info_union: zx_profile_info_union {
+                priority_params: priority_params { priority, _padding2: [0; 20] },
+            },
+            cpu_affinity_mask: affinity,
+        }
+    }
+
+    /// Builds a deadline-scheduling profile.
+    pub fn new_deadline(params: zx_sched_deadline_params_t, affinity: zx_cpu_set_t) -> Self {
+        zx_profile_info_t {
+            flags: ZX_PROFILE_INFO_FLAG_DEADLINE,
+            _padding1: Default::default(),
+            zx_profile_info_union: zx_profile_info_union { deadline_params: params },
+            cpu_affinity_mask: affinity,
+        }
+    }
+
+    /// The flags describing which union variant (if any) is populated.
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    /// Reads the priority variant, or `None` if `flags` doesn't have
+    /// `ZX_PROFILE_INFO_FLAG_PRIORITY` set.
+    pub fn as_priority(&self) -> Option<i32> {
+        if self.flags & ZX_PROFILE_INFO_FLAG_PRIORITY == 0 {
+            return None;
+        }
+        // Safety: `flags` confirms the union was populated as `priority_params`.
+        Some(unsafe { self.zx_profile_info_union.priority_params.priority })
+    }
+
+    /// Reads the deadline variant, or `None` if `flags` doesn't have
+    /// `ZX_PROFILE_INFO_FLAG_DEADLINE` set.
+    pub fn as_deadline(&self) -> Option<zx_sched_deadline_params_t> {
+        if self.flags & ZX_PROFILE_INFO_FLAG_DEADLINE == 0 {
+            return None;
+        }
+        // Safety: `flags` confirms the union was populated as `deadline_params`.
+        Some(unsafe { self.zx_profile_info_union.deadline_params })
+    }
+}
+
+// source: zircon/system/public/zircon/syscalls/exception.h
+//
+// Hand transcribed just enough of the exception-report ABI (not otherwise present in this
+// crate) to give `zx_restricted_exception_t` below a real `exception` field, following the same
+// per-architecture layout pattern already used for `zx_packet_guest_mem_t`.
+pub type zx_excp_type_t = u32;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_exception_header_t {
+    pub size: u32,
+    pub type_: zx_excp_type_t,
+}
+
+#[cfg(target_arch = "x86_64")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_exception_context_t {
+    pub vector: u64,
+    pub err_code: u64,
+    pub cr2: u64,
+}
+
+#[cfg(target_arch = "aarch64")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_exception_context_t {
+    pub esr: u32,
+    pub _padding1: [u8; 4],
+    pub far: u64,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_exception_report_t {
+    pub header: zx_exception_header_t,
+    pub context: zx_exception_context_t,
+}
+
+// source: zircon/system/public/zircon/syscalls/restricted.h
+pub type zx_restricted_reason_t = u64;
+
+multiconst!(zx_restricted_reason_t, [
+    ZX_RESTRICTED_REASON_SYSCALL   = 0;
+    ZX_RESTRICTED_REASON_EXCEPTION = 1;
+    ZX_RESTRICTED_REASON_KICK      = 2;
+]);
+
+#[cfg(target_arch = "x86_64")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_restricted_state_t {
+    pub rdi: u64,
+    pub rsi: u64,
+    pub rbp: u64,
+    pub rbx: u64,
+    pub rdx: u64,
+    pub rcx: u64,
+    pub rax: u64,
+    pub rsp: u64,
+    pub r8: u64,
+    pub r9: u64,
+    pub r10: u64,
+    pub r11: u64,
+    pub r12: u64,
+    pub r13: u64,
+    pub r14: u64,
+    pub r15: u64,
+    pub rip: u64,
+    pub rflags: u64,
+    pub fs_base: u64,
+    pub gs_base: u64,
+}
+
+#[cfg(target_arch = "aarch64")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_restricted_state_t {
+    pub x: [u64; 31],
+    pub sp: u64,
+    pub pc: u64,
+    pub tpidr: u64,
+    pub cpsr: u32,
+    pub _padding1: [u8; 4],
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_restricted_syscall_t {
+    pub state: zx_restricted_state_t,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_restricted_exception_t {
+    pub state: zx_restricted_state_t,
+    pub exception: zx_exception_report_t,
+}
+
+// source: zircon/system/public/zircon/syscalls/system.h
+//
+// Describes a CPU power level (a "P-state") and the cost of transitioning between two of them,
+// pairing with the `zx_sched_deadline_params_t` / `zx_cpu_set_t` affinity machinery above for a
+// power-aware scheduler.
+pub type zx_processor_power_level_options_t = u64;
+
+multiconst!(zx_processor_power_level_options_t, [
+    ZX_PROCESSOR_POWER_LEVEL_OPTIONS_DOMAIN_INDEPENDENT = 1 << 0;
+]);
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_processor_power_level_t {
+    pub options: u64,
+    pub processing_rate: u64,
+    pub power_coefficient_nw: u64,
+    pub control_interface: u64,
+    pub control_argument: u64,
+    pub diagnostic_name: [u8; ZX_MAX_NAME_LEN],
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct zx_processor_power_level_transition_t {
+    pub latency: zx_duration_t,
+    pub energy_nj: u64,
+    pub from: u8,
+    pub to: u8,
+    pub _padding: [u8; 6],
+}
+
+// The raw FFI binding surface. Gated to Fuchsia targets so this crate's type layer (everything
+// above this point) still compiles and is usable for cross-platform tooling that only needs the
+// ABI constants/structs, without requiring a `-lzircon` to link against.
+#[cfg(target_os = "fuchsia")]
+#[link(name = "zircon")]
+extern "C" {
+    pub fn zx_nanosleep(deadline: zx_time_t) -> zx_status_t;
+    pub fn zx_ticks_get() -> zx_ticks_t;
+    pub fn zx_ticks_per_second() -> zx_ticks_t;
+
+    pub fn zx_handle_close(handle: zx_handle_t) -> zx_status_t;
+    pub fn zx_handle_duplicate(
+        handle: zx_handle_t,
+        rights: zx_rights_t,
+        out: *mut zx_handle_t,
+    ) -> zx_status_t;
+
+    pub fn zx_object_wait_one(
+        handle: zx_handle_t,
+        signals: zx_signals_t,
+        deadline: zx_time_t,
+        observed: *mut zx_signals_t,
+    ) -> zx_status_t;
+    pub fn zx_object_wait_many(
+        items: *mut zx_wait_item_t,
+        count: u32,
+        deadline: zx_time_t,
+    ) -> zx_status_t;
+    pub fn zx_object_wait_async(
+        handle: zx_handle_t,
+        port: zx_handle_t,
+        key: u64,
+        signals: zx_signals_t,
+        options: u32,
+    ) -> zx_status_t;
+
+    pub fn zx_channel_call(
+        handle: zx_handle_t,
+        options: u32,
+        deadline: zx_time_t,
+        args: *const zx_channel_call_args_t,
+        actual_bytes: *mut u32,
+        actual_handles: *mut u32,
+    ) -> zx_status_t;
+    pub fn zx_channel_call_etc(
+        handle: zx_handle_t,
+        options: u32,
+        deadline: zx_time_t,
+        args: *const zx_channel_call_etc_args_t,
+        actual_bytes: *mut u32,
+        actual_handles: *mut u32,
+    ) -> zx_status_t;
+
+    pub fn zx_clock_create(
+        options: u64,
+        args: *const zx_clock_create_args_v1_t,
+        out: *mut zx_handle_t,
+    ) -> zx_status_t;
+    pub fn zx_clock_update(
+        handle: zx_handle_t,
+        options: u64,
+        args: *const zx_clock_update_args_v1_t,
+    ) -> zx_status_t;
+    pub fn zx_clock_get_details(
+        handle: zx_handle_t,
+        options: u64,
+        details: *mut zx_clock_details_v1_t,
+    ) -> zx_status_t;
+
+    pub fn zx_cache_flush(addr: *const u8, len: usize, options: u32) -> zx_status_t;
 }
@@ -2,38 +2,139 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
+pub mod blackout;
 pub mod operator;
 
 use {
     fidl_fuchsia_hardware_block_partition::Guid,
-    fs_management::Minfs,
+    fs_management::asynchronous::{Blobfs, Fxfs, Minfs, ServingFilesystem},
     fuchsia_async::{Task, TimeoutExt},
     fuchsia_zircon::Vmo,
     log::debug,
-    operator::MinfsOperator,
+    operator::{BlobfsOperator, FsOperator, MinfsOperator, TreeDistribution, TreeOperator},
     rand::rngs::SmallRng,
+    std::path::Path,
     std::thread::sleep,
     std::time::Duration,
     stress_test_utils::{get_volume_path, TestInstance},
 };
 
+/// Which order to tear things down in when modeling a disconnect.
+///
+/// `ComponentManagerFirst` kills the component tree while the
+/// block device is still reachable; `RamdiskFirst` instead severs the backing ramdisk first
+/// and polls until its block path disappears before touching the filesystem, so in-flight
+/// writes are rejected exactly as they would be on a real power cut rather than failing
+/// because their process was torn down first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisconnectOrder {
+    ComponentManagerFirst,
+    RamdiskFirst,
+}
+
+/// Polls `block_path` until it no longer exists, modeling the disk "disappearing" out from
+/// under the filesystem on power loss.
+fn wait_for_block_path_gone(block_path: &Path) {
+    while block_path.exists() {
+        sleep(Duration::from_millis(50));
+    }
+}
+
 // All partitions in this test have their type set to this arbitrary GUID.
 const TYPE_GUID: Guid = Guid {
     value: [0x0, 0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8, 0x9, 0xa, 0xb, 0xc, 0xd, 0xe, 0xf],
 };
 
-// The path to the minfs filesystem in the test's namespace
-const MINFS_MOUNT_PATH: &str = "/minfs";
+/// Which fvm-hosted filesystem a `run_test` invocation should stress. `FsConfig::for_kind`
+/// gives its volume name and mount path, and `start_serving` builds the operator that drives
+/// it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FsKind {
+    Minfs,
+    Blobfs,
+    Fxfs,
+}
+
+/// The bits of `run_test` that vary by filesystem kind, gathered in one place instead of
+/// being copy-pasted into a per-fs `run_test`.
+struct FsConfig {
+    kind: FsKind,
+    volume_name: &'static str,
+    mount_path: &'static str,
+}
+
+impl FsConfig {
+    fn for_kind(kind: FsKind) -> Self {
+        match kind {
+            FsKind::Minfs => {
+                Self { kind, volume_name: "minfs", mount_path: "/minfs" }
+            }
+            FsKind::Blobfs => {
+                Self { kind, volume_name: "blobfs", mount_path: "/blobfs" }
+            }
+            FsKind::Fxfs => {
+                Self { kind, volume_name: "fxfs", mount_path: "/fxfs" }
+            }
+        }
+    }
+}
+
+/// Formats, checks and serves `volume_path` as `config.kind`, using the async
+/// `ServingFilesystem` API rather than the synchronous binary wrappers (`Minfs::mount`,
+/// etc.), and returns a boxed `FsOperator` that knows how to drive that filesystem kind.
+async fn start_serving(
+    config: &FsConfig,
+    volume_path: &str,
+    tree_distribution: Option<TreeDistribution>,
+) -> (ServingFilesystem, Box<dyn FsOperator>) {
+    match config.kind {
+        FsKind::Minfs => {
+            let mut fs = Minfs::new(volume_path).unwrap();
+            fs.format().await.unwrap();
+            fs.fsck().await.unwrap();
+            let serving = fs.serve().await.unwrap();
+            let operator: Box<dyn FsOperator> = match tree_distribution {
+                Some(distribution) => {
+                    Box::new(TreeOperator::new_from_fs(config.mount_path, distribution).await)
+                }
+                None => Box::new(MinfsOperator::new_from_fs(config.mount_path).await),
+            };
+            (serving, operator)
+        }
+        FsKind::Blobfs => {
+            let mut fs = Blobfs::new(volume_path).unwrap();
+            fs.format().await.unwrap();
+            fs.fsck().await.unwrap();
+            let serving = fs.serve().await.unwrap();
+            (serving, Box::new(BlobfsOperator::new_from_fs(config.mount_path).await))
+        }
+        FsKind::Fxfs => {
+            let mut fs = Fxfs::new(volume_path).unwrap();
+            fs.format().await.unwrap();
+            fs.fsck().await.unwrap();
+            let serving = fs.serve().await.unwrap();
+            // Fxfs is driven the same way as minfs for the purposes of this harness: it
+            // accepts arbitrary files, unlike blobfs's merkle-addressed writes.
+            (serving, Box::new(MinfsOperator::new_from_fs(config.mount_path).await))
+        }
+    }
+}
 
 pub async fn run_test(
+    fs_kind: FsKind,
     rng: SmallRng,
     ramdisk_block_count: u64,
     ramdisk_block_size: u64,
     fvm_slice_size: u64,
     num_operations: Option<u64>,
     disconnect_secs: u64,
+    disconnect_order: DisconnectOrder,
     time_limit_secs: Option<u64>,
+    tree_distribution: Option<TreeDistribution>,
+    fixture: Vec<blackout::FixtureEntry>,
 ) {
+    let config = FsConfig::for_kind(fs_kind);
+
     // Create the VMO that the ramdisk is backed by
     let vmo_size = ramdisk_block_count * ramdisk_block_size;
     let vmo = Vmo::create(vmo_size).unwrap();
@@ -41,57 +142,68 @@ pub async fn run_test(
     // Initialize the ramdisk and setup FVM.
     let mut instance = TestInstance::init(&vmo, fvm_slice_size, ramdisk_block_size).await;
 
-    // Create a minfs volume
-    let volume_instance_guid = instance.new_volume("minfs", TYPE_GUID).await;
+    // Create a volume of the requested filesystem kind
+    let volume_instance_guid = instance.new_volume(config.volume_name, TYPE_GUID).await;
 
     // Find the path to the volume
     let block_path = instance.block_path();
     let mut volume_path = get_volume_path(block_path, &volume_instance_guid).await;
 
-    // Initialize minfs for the first time
-    let mut minfs = Minfs::new(volume_path.to_str().unwrap()).unwrap();
-    minfs.format().unwrap();
+    let (mut serving_fs, operator) = if disconnect_secs > 0 {
+        let (serving_fs, operator) =
+            start_serving(&config, volume_path.to_str().unwrap(), tree_distribution).await;
 
-    if disconnect_secs > 0 {
+        let config_for_task = FsConfig::for_kind(fs_kind);
         Task::blocking(async move {
             // Crash the block device every |disconnect_secs|.
             loop {
-                {
-                    // Start up minfs
-                    let mut minfs = Minfs::new(volume_path.to_str().unwrap()).unwrap();
-                    minfs.fsck().unwrap();
-                    minfs.mount(MINFS_MOUNT_PATH).unwrap();
-
-                    // Wait for the required amount of time
-                    sleep(Duration::from_secs(disconnect_secs));
-
-                    // Crash the old instance and replace it with a new instance.
-                    // This will cause the component tree to be taken down abruptly.
-                    debug!("Killing component manager");
-                    instance.kill_component_manager();
-
-                    // Minfs may not neatly terminate. Force kill the process.
-                    let result = minfs.kill();
-                    debug!("Minfs kill result = {:?}", result);
+                // Wait for the required amount of time
+                sleep(Duration::from_secs(disconnect_secs));
+
+                match disconnect_order {
+                    DisconnectOrder::ComponentManagerFirst => {
+                        // Crash the old instance and replace it with a new instance.
+                        // This will cause the component tree to be taken down abruptly.
+                        debug!("Killing component manager");
+                        instance.kill_component_manager();
+                    }
+                    DisconnectOrder::RamdiskFirst => {
+                        // Sever the backing ramdisk first and wait for it to actually
+                        // disappear, so the filesystem observes writes failing exactly as it
+                        // would on sudden power loss, rather than being torn down itself
+                        // before the device is gone.
+                        debug!("Severing ramdisk before filesystem");
+                        instance.kill_ramdisk();
+                        wait_for_block_path_gone(instance.block_path());
+                        instance.kill_component_manager();
+                    }
                 }
 
                 // Start up a new instance
                 instance = TestInstance::existing(&vmo, ramdisk_block_size).await;
                 let block_path = instance.block_path();
                 volume_path = get_volume_path(block_path, &volume_instance_guid).await;
+                let _ =
+                    start_serving(&config_for_task, volume_path.to_str().unwrap(), tree_distribution)
+                        .await;
             }
         })
         .detach();
+
+        (serving_fs, operator)
     } else {
-        // Start up minfs
-        minfs.fsck().unwrap();
-        minfs.mount(MINFS_MOUNT_PATH).unwrap();
+        start_serving(&config, volume_path.to_str().unwrap(), tree_distribution).await
+    };
+
+    // Pre-populate the filesystem with the caller-supplied fixture before the stress loop
+    // begins. The manifest `preload_fixture` returns is not verified here.
+    if !fixture.is_empty() {
+        blackout::preload_fixture(Path::new(config.mount_path), &fixture);
     }
 
     // Run the operator in a new thread
     let operator_task = Task::blocking(async move {
-        let operator = MinfsOperator::new(rng).await;
-        operator.do_random_operations(num_operations.unwrap_or(u64::MAX)).await;
+        operator.do_random_operations(rng, num_operations.unwrap_or(u64::MAX)).await;
     });
 
     if let Some(time_limit_secs) = time_limit_secs {
@@ -99,4 +211,6 @@ pub async fn run_test(
     } else {
         operator_task.await;
     };
+
+    serving_fs.kill().await.ok();
 }
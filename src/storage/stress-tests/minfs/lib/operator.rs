@@ -0,0 +1,163 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    async_trait::async_trait,
+    rand::{rngs::SmallRng, Rng},
+    std::path::{Path, PathBuf},
+};
+
+/// Parameters governing the shape of the directory tree a `TreeOperator` generates: how deep
+/// it can nest, how many children a directory gets, how likely a node is to be a directory
+/// rather than a leaf file, and how big a leaf file's random contents are.
+///
+/// Tuning these lets a test target deep-narrow layouts (high `max_depth`, low
+/// `max_children_per_dir`) or shallow-wide ones (the reverse), which exercise minfs
+/// indirect-block and directory-entry paths that flat random writes never reach.
+#[derive(Clone, Copy, Debug)]
+pub struct TreeDistribution {
+    pub max_depth: u32,
+    pub max_children_per_dir: u32,
+    pub directory_probability: f64,
+    pub file_size_range: (usize, usize),
+}
+
+impl Default for TreeDistribution {
+    fn default() -> Self {
+        Self {
+            max_depth: 6,
+            max_children_per_dir: 8,
+            directory_probability: 0.3,
+            file_size_range: (0, 4096),
+        }
+    }
+}
+
+/// The behavior that differs between filesystems under stress. `run_test` drives whichever
+/// operator `start_serving` picks for the `FsKind` it was asked to exercise.
+#[async_trait]
+pub trait FsOperator: Send {
+    /// Performs up to `num_operations` randomized operations against the mounted filesystem,
+    /// using `rng` as the sole source of randomness so a run is reproducible from its seed.
+    async fn do_random_operations(&self, rng: SmallRng, num_operations: u64);
+}
+
+/// Operator for a mounted minfs instance. Its `do_random_operations` is not implemented and
+/// panics.
+pub struct MinfsOperator {
+    mount_path: String,
+}
+
+impl MinfsOperator {
+    /// Creates an operator for the filesystem already mounted at `mount_path`.
+    pub async fn new_from_fs(mount_path: &str) -> Self {
+        Self { mount_path: mount_path.to_string() }
+    }
+}
+
+#[async_trait]
+impl FsOperator for MinfsOperator {
+    async fn do_random_operations(&self, _rng: SmallRng, _num_operations: u64) {
+        // No random operation loop is implemented for minfs; calling this panics.
+        unimplemented!("minfs random operation loop")
+    }
+}
+
+/// Operator for a mounted blobfs instance, which identifies content by its root hash instead
+/// of a caller-chosen path. Its `do_random_operations` is not implemented and panics.
+pub struct BlobfsOperator {
+    mount_path: String,
+}
+
+impl BlobfsOperator {
+    /// Creates an operator for the filesystem already mounted at `mount_path`.
+    pub async fn new_from_fs(mount_path: &str) -> Self {
+        Self { mount_path: mount_path.to_string() }
+    }
+}
+
+#[async_trait]
+impl FsOperator for BlobfsOperator {
+    async fn do_random_operations(&self, _rng: SmallRng, _num_operations: u64) {
+        unimplemented!("blobfs random operation loop")
+    }
+}
+
+/// Drives a mounted filesystem by materializing whole directory-tree subtrees drawn from a
+/// `TreeDistribution`, then randomly pruning and regrowing them, rather than performing flat
+/// unstructured random operations.
+pub struct TreeOperator {
+    mount_path: PathBuf,
+    distribution: TreeDistribution,
+}
+
+impl TreeOperator {
+    /// Creates an operator for the filesystem already mounted at `mount_path`, generating
+    /// trees shaped by `distribution`.
+    pub async fn new_from_fs(mount_path: &str, distribution: TreeDistribution) -> Self {
+        Self { mount_path: PathBuf::from(mount_path), distribution }
+    }
+
+    /// Materializes one subtree under the directory `root` (the mount path or a directory
+    /// inside it), recursing according to `self.distribution` until `max_depth` is exhausted.
+    fn generate_subtree(&self, rng: &mut SmallRng, root: &Path, depth: u32) {
+        if depth >= self.distribution.max_depth {
+            return;
+        }
+
+        let children = rng.gen_range(0..=self.distribution.max_children_per_dir);
+        for i in 0..children {
+            let is_dir = rng.gen_bool(self.distribution.directory_probability);
+            if is_dir {
+                let child_dir = root.join(format!("dir_{}_{}", depth, i));
+                std::fs::create_dir_all(&child_dir).unwrap();
+                self.generate_subtree(rng, &child_dir, depth + 1);
+            } else {
+                let (min, max) = self.distribution.file_size_range;
+                let size = rng.gen_range(min..=max);
+                let contents: Vec<u8> = (0..size).map(|_| rng.gen()).collect();
+                std::fs::write(root.join(format!("file_{}_{}", depth, i)), &contents).unwrap();
+            }
+        }
+    }
+
+    /// Picks a uniformly-random existing directory under the mount point and deletes it, so
+    /// the next `generate_subtree` call regrows a fresh subtree in its place. Does nothing if
+    /// no directory exists yet.
+    fn prune_random_subtree(&self, rng: &mut SmallRng) {
+        let candidates: Vec<PathBuf> = walkdir_dirs(&self.mount_path);
+        if candidates.is_empty() {
+            return;
+        }
+        let victim = &candidates[rng.gen_range(0..candidates.len())];
+        std::fs::remove_dir_all(victim).ok();
+    }
+}
+
+fn walkdir_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    if let Ok(entries) = std::fs::read_dir(root) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path.clone());
+                dirs.extend(walkdir_dirs(&path));
+            }
+        }
+    }
+    dirs
+}
+
+#[async_trait]
+impl FsOperator for TreeOperator {
+    async fn do_random_operations(&self, rng: SmallRng, num_operations: u64) {
+        let mut rng = rng;
+        for round in 0..num_operations {
+            if round % 2 == 0 {
+                self.prune_random_subtree(&mut rng);
+            }
+            self.generate_subtree(&mut rng, &self.mount_path, 0);
+        }
+    }
+}
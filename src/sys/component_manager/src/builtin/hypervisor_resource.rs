@@ -3,13 +3,21 @@
 // found in the LICENSE file.
 
 use {
-    crate::{builtin::capability::BuiltinCapability, capability::*},
-    anyhow::{format_err, Error},
+    crate::{
+        builtin::{
+            capability::BuiltinCapability,
+            system_resource::{
+                ResourceGetResponder, ResourceRequestStream, SystemResource, SystemResourceKind,
+            },
+        },
+        capability::*,
+    },
+    anyhow::Error,
     async_trait::async_trait,
     cm_rust::CapabilityName,
     fidl_fuchsia_kernel as fkernel,
-    fuchsia_zircon::{self as zx, HandleBased, Resource},
-    futures::prelude::*,
+    fuchsia_inspect::Node,
+    fuchsia_zircon::{self as zx, Resource},
     lazy_static::lazy_static,
     std::sync::Arc,
 };
@@ -19,15 +27,48 @@ lazy_static! {
         "fuchsia.kernel.HypervisorResource".into();
 }
 
+/// Identifies the HYPERVISOR resource's expected `(kind, base, size)` to `SystemResource`.
+pub struct HypervisorResourceKind;
+
+impl SystemResourceKind for HypervisorResourceKind {
+    const NAME: &'static str = "HYPERVISOR";
+    const KIND: zx::sys::zx_rsrc_kind_t = zx::sys::ZX_RSRC_KIND_SYSTEM;
+    const BASE: u64 = zx::sys::ZX_RSRC_SYSTEM_HYPERVISOR_BASE;
+    const SIZE: usize = 1;
+}
+
+impl ResourceRequestStream for fkernel::HypervisorResourceRequestStream {
+    type Request = fkernel::HypervisorResourceRequest;
+    type GetResponder = fkernel::HypervisorResourceGetResponder;
+
+    fn into_get(request: Self::Request) -> Self::GetResponder {
+        let fkernel::HypervisorResourceRequest::Get { responder } = request;
+        responder
+    }
+}
+
+impl ResourceGetResponder for fkernel::HypervisorResourceGetResponder {
+    fn send(self, resource: Resource) -> Result<(), fidl::Error> {
+        fkernel::HypervisorResourceGetResponder::send(self, resource)
+    }
+}
+
 /// An implementation of fuchsia.kernel.HypervisorResource protocol.
 pub struct HypervisorResource {
-    resource: Resource,
+    system_resource: SystemResource<HypervisorResourceKind>,
 }
 
 impl HypervisorResource {
-    /// `resource` must be the Hypervisor resource.
-    pub fn new(resource: Resource) -> Arc<Self> {
-        Arc::new(Self { resource })
+    /// `resource` must be the Hypervisor resource. Inspect properties (request counts, last
+    /// served time, validation failures, resource kind/base/size) are published under `node`.
+    pub fn new(resource: Resource, node: &Node) -> Arc<Self> {
+        Arc::new(Self { system_resource: SystemResource::new(resource, node) })
+    }
+
+    /// Like `new`, but duplicates `resource` with `rights` on every `Get` instead of
+    /// `SAME_RIGHTS`, for routing a restricted view of the resource to a less-trusted consumer.
+    pub fn new_with_rights(resource: Resource, rights: zx::Rights, node: &Node) -> Arc<Self> {
+        Arc::new(Self { system_resource: SystemResource::new_with_rights(resource, rights, node) })
     }
 }
 
@@ -38,21 +79,9 @@ impl BuiltinCapability for HypervisorResource {
 
     async fn serve(
         self: Arc<Self>,
-        mut stream: fkernel::HypervisorResourceRequestStream,
+        stream: fkernel::HypervisorResourceRequestStream,
     ) -> Result<(), Error> {
-        let resource_info = self.resource.info()?;
-        if (resource_info.kind != zx::sys::ZX_RSRC_KIND_SYSTEM
-            || resource_info.base != zx::sys::ZX_RSRC_SYSTEM_HYPERVISOR_BASE
-            || resource_info.size != 1)
-        {
-            return Err(format_err!("HYPERVISOR resource not available."));
-        }
-        while let Some(fkernel::HypervisorResourceRequest::Get { responder }) =
-            stream.try_next().await?
-        {
-            responder.send(self.resource.duplicate_handle(zx::Rights::SAME_RIGHTS)?)?;
-        }
-        Ok(())
+        self.system_resource.serve(stream).await
     }
 
     fn matches_routed_capability(&self, capability: &InternalCapability) -> bool {
@@ -100,9 +129,9 @@ mod tests {
         let (proxy, stream) =
             fidl::endpoints::create_proxy_and_stream::<fkernel::HypervisorResourceMarker>()?;
         fasync::Task::local(
-            HypervisorResource::new(hypervisor_resource).serve(stream).unwrap_or_else(|e| {
-                panic!("Error while serving HYPERVISOR resource service: {}", e)
-            }),
+            HypervisorResource::new(hypervisor_resource, &Node::default())
+                .serve(stream)
+                .unwrap_or_else(|e| panic!("Error while serving HYPERVISOR resource service: {}", e)),
         )
         .detach();
         Ok(proxy)
@@ -115,7 +144,7 @@ mod tests {
         }
         let (_, stream) =
             fidl::endpoints::create_proxy_and_stream::<fkernel::HypervisorResourceMarker>()?;
-        assert!(!HypervisorResource::new(Resource::from(zx::Handle::invalid()))
+        assert!(!HypervisorResource::new(Resource::from(zx::Handle::invalid()), &Node::default())
             .serve(stream)
             .await
             .is_ok());
@@ -143,7 +172,8 @@ mod tests {
             return Ok(());
         }
 
-        let hypervisor_resource = HypervisorResource::new(get_hypervisor_resource().await?);
+        let hypervisor_resource =
+            HypervisorResource::new(get_hypervisor_resource().await?, &Node::default());
         let hooks = Hooks::new(None);
         hooks.install(hypervisor_resource.hooks()).await;
 
@@ -3,13 +3,21 @@
 // found in the LICENSE file.
 
 use {
-    crate::{builtin::capability::BuiltinCapability, capability::*},
+    crate::{
+        builtin::{
+            capability::BuiltinCapability,
+            system_resource::{
+                ResourceGetResponder, ResourceRequestStream, SystemResource, SystemResourceKind,
+            },
+        },
+        capability::*,
+    },
     anyhow::Error,
     async_trait::async_trait,
     cm_rust::CapabilityName,
     fidl_fuchsia_boot as fboot,
-    fuchsia_zircon::{self as zx, HandleBased, Resource},
-    futures::prelude::*,
+    fuchsia_inspect::Node,
+    fuchsia_zircon::{self as zx, Resource},
     lazy_static::lazy_static,
     std::sync::Arc,
 };
@@ -18,14 +26,50 @@ lazy_static! {
     static ref ROOT_RESOURCE_CAPABILITY_NAME: CapabilityName = "fuchsia.boot.RootResource".into();
 }
 
+/// Identifies the ROOT resource's expected `(kind, base, size)` to `SystemResource`. Unlike the
+/// hand-rolled version this replaces, `Get` requests are now validated the same way every other
+/// resource's are, rather than being served unconditionally.
+pub struct RootResourceKind;
+
+impl SystemResourceKind for RootResourceKind {
+    const NAME: &'static str = "ROOT";
+    const KIND: zx::sys::zx_rsrc_kind_t = zx::sys::ZX_RSRC_KIND_ROOT;
+    const BASE: u64 = 0;
+    const SIZE: usize = 1;
+}
+
+impl ResourceRequestStream for fboot::RootResourceRequestStream {
+    type Request = fboot::RootResourceRequest;
+    type GetResponder = fboot::RootResourceGetResponder;
+
+    fn into_get(request: Self::Request) -> Self::GetResponder {
+        let fboot::RootResourceRequest::Get { responder } = request;
+        responder
+    }
+}
+
+impl ResourceGetResponder for fboot::RootResourceGetResponder {
+    fn send(self, resource: Resource) -> Result<(), fidl::Error> {
+        fboot::RootResourceGetResponder::send(self, resource)
+    }
+}
+
 /// An implementation of the `fuchsia.boot.RootResource` protocol.
 pub struct RootResource {
-    resource: Resource,
+    system_resource: SystemResource<RootResourceKind>,
 }
 
 impl RootResource {
-    pub fn new(resource: Resource) -> Arc<Self> {
-        Arc::new(Self { resource })
+    /// Inspect properties (request counts, last served time, validation failures, resource
+    /// kind/base/size) are published under `node`.
+    pub fn new(resource: Resource, node: &Node) -> Arc<Self> {
+        Arc::new(Self { system_resource: SystemResource::new(resource, node) })
+    }
+
+    /// Like `new`, but duplicates `resource` with `rights` on every `Get` instead of
+    /// `SAME_RIGHTS`, for routing a restricted view of the resource to a less-trusted consumer.
+    pub fn new_with_rights(resource: Resource, rights: zx::Rights, node: &Node) -> Arc<Self> {
+        Arc::new(Self { system_resource: SystemResource::new_with_rights(resource, rights, node) })
     }
 }
 
@@ -36,12 +80,9 @@ impl BuiltinCapability for RootResource {
 
     async fn serve(
         self: Arc<Self>,
-        mut stream: fboot::RootResourceRequestStream,
+        stream: fboot::RootResourceRequestStream,
     ) -> Result<(), Error> {
-        while let Some(fboot::RootResourceRequest::Get { responder }) = stream.try_next().await? {
-            responder.send(self.resource.duplicate_handle(zx::Rights::SAME_RIGHTS)?)?;
-        }
-        Ok(())
+        self.system_resource.serve(stream).await
     }
 
     fn matches_routed_capability(&self, capability: &InternalCapability) -> bool {
@@ -63,7 +104,8 @@ mod tests {
 
     #[fasync::run_singlethreaded(test)]
     async fn can_connect() -> Result<(), Error> {
-        let root_resource = RootResource::new(Resource::from(zx::Handle::invalid()));
+        let root_resource =
+            RootResource::new(Resource::from(zx::Handle::invalid()), &Node::default());
         let hooks = Hooks::new(None);
         hooks.install(root_resource.hooks()).await;
 
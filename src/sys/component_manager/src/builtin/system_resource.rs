@@ -0,0 +1,118 @@
+// Copyright 2020 The Fuchsia Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use {
+    anyhow::{format_err, Error},
+    fuchsia_inspect::{IntProperty, Node, NumericProperty, UintProperty},
+    fuchsia_zircon::{self as zx, HandleBased, Resource},
+    futures::prelude::*,
+    std::marker::PhantomData,
+};
+
+/// The kernel `zx::Resource` `(kind, base, size)` triple a builtin resource capability expects to
+/// be handed, plus the name used in its validation error message. Implemented once per kernel
+/// resource (MMIO, IRQ, Hypervisor, ...) instead of hand-rolling a whole new `BuiltinCapability`
+/// file per resource.
+pub trait SystemResourceKind {
+    /// Name used in the validation error, e.g. "HYPERVISOR".
+    const NAME: &'static str;
+    const KIND: zx::sys::zx_rsrc_kind_t;
+    const BASE: u64;
+    const SIZE: usize;
+}
+
+/// A `Get`-style FIDL request stream that can be driven by `SystemResource::serve`: every
+/// resource-vending protocol in this module has exactly one method, `Get`, that replies with a
+/// duplicated resource handle.
+pub trait ResourceRequestStream: Stream<Item = Result<Self::Request, fidl::Error>> + Unpin {
+    type Request;
+    type GetResponder: ResourceGetResponder;
+
+    /// Extracts the `Get` responder from `request`.
+    fn into_get(request: Self::Request) -> Self::GetResponder;
+}
+
+/// The responder half of a `Get` request, common to every resource-vending protocol.
+pub trait ResourceGetResponder {
+    fn send(self, resource: Resource) -> Result<(), fidl::Error>;
+}
+
+/// A builtin capability backed by a single `zx::Resource`, validated against `K`'s expected
+/// `(kind, base, size)` before any request is served. `HypervisorResource`, `RootResource`, and
+/// other kernel resource capabilities are thin wrappers around this, plugging in their own FIDL
+/// marker/request-stream types and `SystemResourceKind`.
+///
+/// Publishes an Inspect subtree under the `node` it's constructed with, so `fx iquery` can show
+/// how often a privileged kernel resource is being consumed: the number of `Get` requests served,
+/// the monotonic time of the last one, how many times validation has failed, and the resource's
+/// actual `kind`/`base`/`size` as read from `resource.info()`.
+pub struct SystemResource<K> {
+    resource: Resource,
+    rights: zx::Rights,
+    served_count: UintProperty,
+    validation_failure_count: UintProperty,
+    last_served_time_nanos: IntProperty,
+    // Only ever set once at construction time; kept alive so their Inspect values persist.
+    _kind_property: UintProperty,
+    _base_property: UintProperty,
+    _size_property: UintProperty,
+    _kind: PhantomData<K>,
+}
+
+impl<K: SystemResourceKind> SystemResource<K> {
+    /// Serves `resource` duplicated with `zx::Rights::SAME_RIGHTS` on every `Get`.
+    pub fn new(resource: Resource, node: &Node) -> Self {
+        Self::new_with_rights(resource, zx::Rights::SAME_RIGHTS, node)
+    }
+
+    /// Serves `resource` duplicated with `rights` (intersected with the rights `resource` itself
+    /// holds, since `duplicate_handle` can never grant more than the source handle has) on every
+    /// `Get`, so less-trusted consumers of the same protocol can be routed a restricted view of
+    /// the resource instead of a fully-powered one.
+    pub fn new_with_rights(resource: Resource, rights: zx::Rights, node: &Node) -> Self {
+        let (kind, base, size) =
+            resource.info().map(|info| (info.kind, info.base, info.size)).unwrap_or((0, 0, 0));
+        Self {
+            rights,
+            served_count: node.create_uint("get_requests_served", 0),
+            validation_failure_count: node.create_uint("validation_failures", 0),
+            last_served_time_nanos: node.create_int("last_served_time_nanos", 0),
+            _kind_property: node.create_uint("resource_kind", kind as u64),
+            _base_property: node.create_uint("resource_base", base),
+            _size_property: node.create_uint("resource_size", size as u64),
+            resource,
+            _kind: PhantomData,
+        }
+    }
+
+    pub fn resource(&self) -> &Resource {
+        &self.resource
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let info = self.resource.info().map_err(|e| {
+            self.validation_failure_count.add(1);
+            e
+        })?;
+        if info.kind != K::KIND || info.base != K::BASE || info.size != K::SIZE {
+            self.validation_failure_count.add(1);
+            return Err(format_err!("{} resource not available.", K::NAME));
+        }
+        Ok(())
+    }
+
+    /// Validates the wrapped resource against `K`, then replies to every `Get` request on
+    /// `stream` with a duplicate carrying the rights given at construction (`SAME_RIGHTS` for
+    /// `new`) until the channel closes, recording each one served.
+    pub async fn serve<S: ResourceRequestStream>(&self, mut stream: S) -> Result<(), Error> {
+        self.validate()?;
+        while let Some(request) = stream.try_next().await? {
+            let responder = S::into_get(request);
+            responder.send(self.resource.duplicate_handle(self.rights)?)?;
+            self.served_count.add(1);
+            self.last_served_time_nanos.set(zx::Time::get_monotonic().into_nanos());
+        }
+        Ok(())
+    }
+}